@@ -1,6 +1,6 @@
 import { AnchorProvider, Wallet } from "@coral-xyz/anchor";
 import { Connection, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
-import { TrustNetClient, arbiterPda, arbiterVaultPda, voteCommitmentPda } from "@trustnet/sdk";
+import { TrustNetClient, arbiterPda, arbiterRegistryPda } from "@trustnet/sdk";
 import crypto from "crypto";
 
 const SOL = LAMPORTS_PER_SOL;
@@ -68,6 +68,9 @@ export async function runDisputePath(): Promise<void> {
 
   // Register arbiters
   console.log("⚖️ Registering arbiters (1 SOL stake each)...");
+  if (!(await connection.getAccountInfo(arbiterRegistryPda()[0]))) {
+    await clientSdk.initArbiterRegistry();
+  }
   const arbiterStake = BigInt(1 * SOL);
   for (let i = 0; i < 3; i++) {
     await arbiterSdks[i].registerArbiter(arbiterStake);
@@ -106,20 +109,42 @@ export async function runDisputePath(): Promise<void> {
   );
   console.log("   ✓ Dispute raised:", dispute.toBase58(), "\n");
 
+  // Draw the panel
+  console.log("🎲 Drawing arbiter panel...");
+  const panel = await clientSdk.selectArbiters(dispute);
+  const arbiterKeypairs = [arbiter1Keypair, arbiter2Keypair, arbiter3Keypair];
+  // Arbiters registered by earlier runs can be drawn too; only ours vote here.
+  const drawn = [0, 1, 2].filter((i) => panel.some((a) => a.equals(arbiterPda(arbiterKeypairs[i].publicKey)[0])));
+  console.log(`   ✓ ${drawn.length} of our arbiters drawn\n`);
+  if (drawn.length < panel.length) {
+    // Resolution would wait for the reveal deadline, since the other arbiters never reveal.
+    console.log("   Other arbiters were drawn; resolution waits for the reveal deadline. Stopping here.\n");
+    return;
+  }
+
   // Arbiters commit votes
   console.log("🗳️ Arbiters committing votes...");
   const salts = [randomBytes(16), randomBytes(16), randomBytes(16)];
   const votes = [true, true, false]; // 2 vote for provider, 1 for client
 
-  for (let i = 0; i < 3; i++) {
+  for (const i of drawn) {
     await arbiterSdks[i].commitVote(dispute, votes[i], salts[i]);
     console.log(`   ✓ Arbiter ${i + 1} committed`);
   }
   console.log();
 
+  // Reveals open once the commit deadline has passed on-chain
+  const { commitDeadline } = await (clientSdk.program.account as any).dispute.fetch(dispute);
+  console.log(`⏳ Waiting for the commit deadline (${new Date(commitDeadline.toNumber() * 1000).toISOString()})...`);
+  const chainTime = async () => (await connection.getBlockTime(await connection.getSlot())) ?? 0;
+  while ((await chainTime()) <= commitDeadline.toNumber()) {
+    await new Promise((resolve) => setTimeout(resolve, 10_000));
+  }
+  console.log();
+
   // Arbiters reveal votes
   console.log("🔓 Arbiters revealing votes...");
-  for (let i = 0; i < 3; i++) {
+  for (const i of drawn) {
     await arbiterSdks[i].revealVote(dispute, votes[i], salts[i]);
     const voteLabel = votes[i] ? "Provider" : "Client";
     console.log(`   ✓ Arbiter ${i + 1} revealed: ${voteLabel}`);
@@ -128,14 +153,7 @@ export async function runDisputePath(): Promise<void> {
 
   // Resolve dispute
   console.log("⚖️ Resolving dispute...");
-  const arbiterAccounts = [arbiter1Keypair, arbiter2Keypair, arbiter3Keypair].map((kp) => {
-    const [arbiter] = arbiterPda(kp.publicKey);
-    const [arbiterVault] = arbiterVaultPda(kp.publicKey);
-    const [voteCommitment] = voteCommitmentPda(dispute, arbiter);
-    return { arbiter, arbiterVault, voteCommitment, authority: kp.publicKey };
-  });
-
-  await clientSdk.resolveDispute(dispute, clientKeypair.publicKey, providerKeypair.publicKey, arbiterAccounts);
+  await clientSdk.resolveDispute(dispute, clientKeypair.publicKey, providerKeypair.publicKey);
 
   const jobData = await clientSdk.getJob(job);
   console.log("   ✓ Dispute resolved");
@@ -151,5 +169,5 @@ export async function runDisputePath(): Promise<void> {
   console.log();
 
   console.log("─".repeat(60));
-  console.log("✨ Dispute path complete! (Provider won 2-1)\n");
+  console.log("✨ Dispute path complete!\n");
 }
@@ -1,6 +1,23 @@
 import { PublicKey } from "@solana/web3.js";
 import { voteHash } from "./instructions";
 
+/** Arbiters per registry page; mirrors `ARBITER_REGISTRY_PAGE_SIZE` in the program. */
+export const ARBITER_REGISTRY_PAGE_SIZE = 16;
+
 export function buildVoteHash(arbiter: PublicKey, dispute: PublicKey, vote: boolean, salt: Uint8Array): Uint8Array {
   return voteHash(arbiter, dispute, vote, salt);
 }
+
+/**
+ * Mirrors the program's vote weight: the square root of stake, scaled from 0.5x at zero
+ * accuracy through 1x at the starting score of 500 to 1.5x at an accuracy of 1000.
+ */
+export function arbiterVoteWeight(stakeLamports: bigint, accuracyScore: number): bigint {
+  let x = stakeLamports;
+  let y = (x + 1n) / 2n;
+  while (y < x) {
+    x = y;
+    y = (x + stakeLamports / x) / 2n;
+  }
+  return (x * BigInt(500 + Math.min(accuracyScore, 1000))) / 1000n;
+}
@@ -1,21 +1,49 @@
 import { AnchorProvider, BN, Program, web3 } from "@coral-xyz/anchor";
-import { Connection, PublicKey } from "@solana/web3.js";
-import { AgentReputationView, CreateJobArgs, JobView, RateJobArgs, VerifyType } from "./types";
+import { Connection, PublicKey, SYSVAR_SLOT_HASHES_PUBKEY } from "@solana/web3.js";
+import {
+  AgentReputationView,
+  CategoryReputationView,
+  CreateJobArgs,
+  Groth16Proof,
+  Groth16VerifyingKey,
+  JobView,
+  MilestoneSpec,
+  ProviderRequirements,
+  RateJobArgs,
+  ReputationScoreView,
+  SubmissionView,
+  VerifyType,
+} from "./types";
 import {
   arbiterPda,
+  arbiterRegistryPda,
+  arbiterRegistryPagePda,
   arbiterVaultPda,
+  bidPda,
+  categoryReputationPda,
   disputePda,
   disputeVaultPda,
   jobPda,
+  jobTokenVaultPda,
   jobVaultPda,
+  milestoneDisputePda,
+  oracleAttestationPda,
+  oracleCommitteePda,
   PROGRAM_ID,
   ratingPda,
   reputationPda,
   repVaultPda,
+  reviewPanelPda,
+  reviewPda,
+  reviewVaultPda,
+  submissionPda,
   treasuryPda,
+  verifyingKeyPda,
   voteCommitmentPda,
 } from "./pda";
-import { voteHash } from "./instructions";
+import { bidHash, verifyingKeyHash, voteHash } from "./instructions";
+import { computeScore } from "./reputation";
+import { ARBITER_REGISTRY_PAGE_SIZE } from "./arbitration";
 
 // Minimal IDL stub - replace with generated IDL from `anchor build`
 const IDL: any = {
@@ -75,6 +103,40 @@ export class TrustNetClient {
       .rpc();
   }
 
+  /**
+   * Start unbonding `amount` of this wallet's reputation stake. It stops counting toward
+   * the score immediately but can only be withdrawn after the unbonding delay. The stake
+   * left must stay above the minimum, unless all of it is unbonded, which closes the profile.
+   */
+  async requestUnstake(amount: bigint): Promise<string> {
+    const [reputation] = reputationPda(this.wallet.publicKey);
+    const [repVault] = repVaultPda(this.wallet.publicKey);
+    return this.program.methods
+      .requestUnstake(new BN(amount.toString()))
+      .accounts({
+        agent: this.wallet.publicKey,
+        reputation,
+        repVault,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /** Withdraw unbonded reputation stake once the unbonding delay has passed. */
+  async withdrawStake(): Promise<string> {
+    const [reputation] = reputationPda(this.wallet.publicKey);
+    const [repVault] = repVaultPda(this.wallet.publicKey);
+    return this.program.methods
+      .withdrawStake()
+      .accounts({
+        agent: this.wallet.publicKey,
+        reputation,
+        repVault,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+
   async getReputation(agent: PublicKey): Promise<AgentReputationView> {
     const [reputation] = reputationPda(agent);
     // Note: requires IDL with account definitions. Cast to any for stub IDL.
@@ -87,32 +149,88 @@ export class TrustNetClient {
       totalDisputesLost: BigInt(account.totalDisputesLost.toString()),
       totalVolume: BigInt(account.totalVolume.toString()),
       avgRating: account.avgRating,
+      bayesianRating: account.bayesianRating,
+      weightedAvgRating: account.weightedAvgRating,
       ratingCount: BigInt(account.ratingCount.toString()),
       stakeAmount: BigInt(account.stakeAmount.toString()),
+      unbondingAmount: BigInt(account.unbondingAmount.toString()),
+      unbondingReadyAt: account.unbondingReadyAt.toNumber(),
+      closed: account.closed,
+      openJobs: account.openJobs,
+      lastActive: account.lastActive.toNumber(),
+    };
+  }
+
+  /** Score computed on-chain by `get_reputation_score`, read from simulated return data. */
+  async getReputationScore(agent: PublicKey): Promise<ReputationScoreView> {
+    const [reputation] = reputationPda(agent);
+    const result: any = await this.program.methods.getReputationScore().accounts({ reputation }).view();
+    return {
+      score: result.score,
+      completionRate: result.completionRate,
+      disputeAdjustment: result.disputeAdjustment.toNumber(),
+      volumeBonus: result.volumeBonus,
+      stakeBonus: result.stakeBonus,
+      ratingAdjustment: result.ratingAdjustment.toNumber(),
+      decayMultiplier: result.decayMultiplier,
+      daysInactive: result.daysInactive.toNumber(),
+    };
+  }
+
+  /** Open this wallet's reputation track for a job category. Needed to accept jobs in it. */
+  async initCategoryReputation(category: number): Promise<PublicKey> {
+    const [reputation] = reputationPda(this.wallet.publicKey);
+    const [categoryReputation] = categoryReputationPda(this.wallet.publicKey, category);
+    await this.program.methods
+      .initCategoryReputation(category)
+      .accounts({
+        agent: this.wallet.publicKey,
+        reputation,
+        categoryReputation,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    return categoryReputation;
+  }
+
+  async getCategoryReputation(agent: PublicKey, category: number): Promise<CategoryReputationView> {
+    const [categoryReputation] = categoryReputationPda(agent, category);
+    const account: any = await (this.program.account as any).categoryReputation.fetch(categoryReputation);
+    return {
+      agent: account.agent,
+      category: account.category,
+      jobsCompleted: BigInt(account.jobsCompleted.toString()),
+      jobsFailed: BigInt(account.jobsFailed.toString()),
+      volume: BigInt(account.volume.toString()),
+      avgRating: account.avgRating,
+      bayesianRating: account.bayesianRating,
+      ratingCount: BigInt(account.ratingCount.toString()),
       lastActive: account.lastActive.toNumber(),
     };
   }
 
+  /** Category score computed on-chain by `get_category_score`; stake comes from the global profile. */
+  async getCategoryScore(agent: PublicKey, category: number): Promise<ReputationScoreView> {
+    const [reputation] = reputationPda(agent);
+    const [categoryReputation] = categoryReputationPda(agent, category);
+    const result: any = await this.program.methods
+      .getCategoryScore()
+      .accounts({ reputation, categoryReputation })
+      .view();
+    return {
+      score: result.score,
+      completionRate: result.completionRate,
+      disputeAdjustment: result.disputeAdjustment.toNumber(),
+      volumeBonus: result.volumeBonus,
+      stakeBonus: result.stakeBonus,
+      ratingAdjustment: result.ratingAdjustment.toNumber(),
+      decayMultiplier: result.decayMultiplier,
+      daysInactive: result.daysInactive.toNumber(),
+    };
+  }
+
   computeScore(view: AgentReputationView, nowTs: number): number {
-    const completed = Number(view.totalJobsCompleted);
-    const failed = Number(view.totalJobsFailed);
-    const disputesWon = Number(view.totalDisputesWon);
-    const disputesLost = Number(view.totalDisputesLost);
-    const total = completed + failed;
-    let base = total === 0 ? 0 : Math.floor((completed / total) * 100);
-    base += disputesWon * 2 - disputesLost * 5;
-    if (completed >= 50) base += 15;
-    else if (completed >= 20) base += 10;
-    else if (completed >= 5) base += 5;
-    const stakeSol = Number(view.stakeAmount) / 1_000_000_000;
-    if (stakeSol >= 50) base += 10;
-    else if (stakeSol >= 10) base += 5;
-    else if (stakeSol >= 1) base += 2;
-    const daysInactive = Math.floor((nowTs - view.lastActive) / 86_400);
-    const multiplier =
-      daysInactive <= 30 ? 1 : daysInactive <= 90 ? 0.95 : daysInactive <= 180 ? 0.9 : daysInactive <= 360 ? 0.8 : 0.7;
-    const adjusted = Math.floor(base * multiplier);
-    return Math.max(0, Math.min(100, adjusted));
+    return computeScore(view, nowTs);
   }
 
   // ─────────────────────────────────────────────────────────────────────────────
@@ -134,6 +252,34 @@ export class TrustNetClient {
       createdAt: account.createdAt.toNumber(),
       submittedAt: account.submittedAt?.toNumber() ?? null,
       completedAt: account.completedAt?.toNumber() ?? null,
+      mint: account.mint ?? null,
+      revisionCount: account.revisionCount,
+      submissionCount: account.submissionCount,
+      submissionHash: account.submissionHash ? new Uint8Array(account.submissionHash) : null,
+      category: account.category ?? null,
+      providerHasReputation: account.providerHasReputation,
+      milestones: (account.milestones ?? []).map((m: any) => ({
+        amount: BigInt(m.amount.toString()),
+        deadline: m.deadline.toNumber(),
+        termsHash: new Uint8Array(m.termsHash),
+        status: Object.keys(m.status)[0].replace(/^./, (c: string) => c.toUpperCase()),
+        submittedAt: m.submittedAt?.toNumber() ?? null,
+        submissionIndex: m.submissionIndex ?? null,
+      })),
+    };
+  }
+
+  /** Fetch one entry of a job's submission history; `index` counts from 0. */
+  async getSubmission(job: PublicKey, index: number): Promise<SubmissionView> {
+    const [submission] = submissionPda(job, index);
+    const account: any = await (this.program.account as any).submission.fetch(submission);
+    return {
+      job: account.job,
+      provider: account.provider,
+      index: account.index,
+      milestone: account.milestone ?? null,
+      submissionHash: new Uint8Array(account.submissionHash),
+      submittedAt: account.submittedAt.toNumber(),
     };
   }
 
@@ -152,33 +298,214 @@ export class TrustNetClient {
       )
       .accounts({
         client: this.wallet.publicKey,
-        provider: args.provider,
+        provider: args.provider ?? PublicKey.default,
         job,
         jobVault: vault,
         treasury,
+        mint: args.mint ?? null,
+        clientTokenAccount: args.clientTokenAccount ?? null,
+        jobTokenVault: args.mint ? jobTokenVaultPda(job)[0] : null,
+        tokenProgram: args.tokenProgram ?? null,
         systemProgram: web3.SystemProgram.programId,
       })
       .rpc();
     return { job, vault };
   }
 
+  /**
+   * Split a created job's escrow into milestones (client only, before acceptance).
+   * Amounts must sum to the escrowed amount.
+   */
+  async defineMilestones(job: PublicKey, milestones: MilestoneSpec[]): Promise<string> {
+    return this.program.methods
+      .defineMilestones(
+        milestones.map((m) => ({
+          amount: new BN(m.amount.toString()),
+          deadline: new BN(m.deadline),
+          termsHash: Array.from(m.termsHash),
+        }))
+      )
+      .accounts({ client: this.wallet.publicKey, job })
+      .rpc();
+  }
+
+  /**
+   * Restrict which providers may accept a created job (client only).
+   */
+  async setJobRequirements(job: PublicKey, requirements: ProviderRequirements): Promise<string> {
+    return this.program.methods
+      .setJobRequirements({
+        minReputationScore: requirements.minReputationScore,
+        minStake: new BN(requirements.minStake.toString()),
+        specializations: requirements.specializations,
+      })
+      .accounts({ client: this.wallet.publicKey, job })
+      .rpc();
+  }
+
+  /**
+   * Tag a created job with a category (client only). Its provider must hold a
+   * reputation track for the category, which settlement then updates.
+   */
+  async setJobCategory(job: PublicKey, category: number): Promise<string> {
+    return this.program.methods
+      .setJobCategory(category)
+      .accounts({ client: this.wallet.publicKey, job })
+      .rpc();
+  }
+
+  // ─────────────────────────────────────────────────────────────────────────────
+  // Bidding
+  // ─────────────────────────────────────────────────────────────────────────────
+
+  /**
+   * Open a sealed-bid reverse auction on an open job (client only).
+   */
+  async openBidding(job: PublicKey, commitDeadline: number, revealDeadline: number): Promise<string> {
+    return this.program.methods
+      .openBidding(new BN(commitDeadline), new BN(revealDeadline))
+      .accounts({ client: this.wallet.publicKey, job })
+      .rpc();
+  }
+
+  async commitBid(job: PublicKey, amount: bigint, salt: Uint8Array): Promise<PublicKey> {
+    const [bid] = bidPda(job, this.wallet.publicKey);
+    const [reputation] = reputationPda(this.wallet.publicKey);
+    const bidderReputation = (await this.connection.getAccountInfo(reputation)) ? reputation : null;
+    const hash = bidHash(this.wallet.publicKey, job, amount, salt);
+    await this.program.methods
+      .commitBid(Array.from(hash))
+      .accounts({
+        bidder: this.wallet.publicKey,
+        job,
+        bidderReputation,
+        bid,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    return bid;
+  }
+
+  async revealBid(job: PublicKey, amount: bigint, salt: Uint8Array): Promise<string> {
+    const [bid] = bidPda(job, this.wallet.publicKey);
+    return this.program.methods
+      .revealBid(new BN(amount.toString()), Array.from(salt))
+      .accounts({ bidder: this.wallet.publicKey, job, bid })
+      .rpc();
+  }
+
+  /**
+   * Award the job to a revealed bid. The client may pick any revealed bid; otherwise
+   * anyone can award the lowest bid after the grace period. The unspent escrow goes back to the client.
+   * @param job - Job PDA
+   * @param bidder - Bidder whose bid is awarded
+   * @param client - Client pubkey (receives the refund)
+   */
+  async awardBid(job: PublicKey, bidder: PublicKey, client: PublicKey): Promise<string> {
+    const [bid] = bidPda(job, bidder);
+    const [jobVault] = jobVaultPda(job);
+    const accounts = {
+      authority: this.wallet.publicKey,
+      job,
+      bid,
+      client,
+      jobVault,
+      systemProgram: web3.SystemProgram.programId,
+    };
+    const method = client.equals(this.wallet.publicKey)
+      ? this.program.methods.selectBid()
+      : this.program.methods.finalizeAuction();
+    return method.accounts(accounts).rpc();
+  }
+
+  /**
+   * Accept a job. Open jobs assign the caller as provider; the caller's reputation PDA
+   * is always passed, so job requirements can be checked and an existing profile counts the job.
+   */
   async acceptJob(job: PublicKey, stakeLamports: bigint): Promise<string> {
     const [vault] = jobVaultPda(job);
+    const [providerReputation] = reputationPda(this.wallet.publicKey);
+    const { category, verificationType } = await this.getJob(job);
+    const providerCategoryReputation =
+      category === null ? null : categoryReputationPda(this.wallet.publicKey, category)[0];
+    const oracleCommittee = "oracleThreshold" in (verificationType as any) ? oracleCommitteePda(job)[0] : null;
+    const reviewPanel = "peerReview" in (verificationType as any) ? reviewPanelPda(job)[0] : null;
     return this.program.methods
       .acceptJob(new BN(stakeLamports.toString()))
       .accounts({
         provider: this.wallet.publicKey,
         job,
+        providerReputation,
+        providerCategoryReputation,
+        oracleCommittee,
+        reviewPanel,
         jobVault: vault,
         systemProgram: web3.SystemProgram.programId,
       })
       .rpc();
   }
 
+  /**
+   * Reputation accounts a settling instruction updates. The provider's and client's
+   * profile PDAs are always passed and the program updates whichever exist; the
+   * provider's category track is passed when the job has a category.
+   */
+  private async settlementReputations(
+    job: PublicKey,
+    client: PublicKey,
+    provider: PublicKey
+  ): Promise<{
+    providerReputation: PublicKey;
+    clientReputation: PublicKey;
+    providerCategoryReputation: PublicKey | null;
+  }> {
+    const [providerReputation] = reputationPda(provider);
+    const [clientReputation] = reputationPda(client);
+    const { category } = await this.getJob(job);
+    const providerCategoryReputation = category === null ? null : categoryReputationPda(provider, category)[0];
+    return { providerReputation, clientReputation, providerCategoryReputation };
+  }
+
   async submitCompletion(job: PublicKey, submissionHash: Uint8Array): Promise<string> {
+    const { submissionCount } = await this.getJob(job);
+    const [submission] = submissionPda(job, submissionCount);
     return this.program.methods
       .submitCompletion(Array.from(submissionHash))
-      .accounts({ provider: this.wallet.publicKey, job })
+      .accounts({ provider: this.wallet.publicKey, job, submission, systemProgram: web3.SystemProgram.programId })
+      .rpc();
+  }
+
+  /**
+   * Send a submission back for rework (client only, ClientApproval jobs).
+   * Once the revision cap in `verificationData[0]` is used up, the rejection opens a dispute;
+   * pass `escalate` in that case so the dispute accounts are created.
+   * @param job - Job PDA
+   * @param feedbackHash - Hash of the client's feedback
+   * @param escalate - Whether this rejection exceeds the revision cap
+   */
+  async rejectSubmission(job: PublicKey, feedbackHash: Uint8Array, escalate = false): Promise<string> {
+    const [jobVault] = jobVaultPda(job);
+    const [dispute] = disputePda(job);
+    const [disputeVault] = disputeVaultPda(dispute);
+    return this.program.methods
+      .rejectSubmission(Array.from(feedbackHash))
+      .accounts({
+        client: this.wallet.publicKey,
+        job,
+        dispute: escalate ? dispute : null,
+        jobVault,
+        disputeVault: escalate ? disputeVault : null,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  async submitMilestone(job: PublicKey, milestoneIndex: number, submissionHash: Uint8Array): Promise<string> {
+    const { submissionCount } = await this.getJob(job);
+    const [submission] = submissionPda(job, submissionCount);
+    return this.program.methods
+      .submitMilestone(milestoneIndex, Array.from(submissionHash))
+      .accounts({ provider: this.wallet.publicKey, job, submission, systemProgram: web3.SystemProgram.programId })
       .rpc();
   }
 
@@ -190,6 +517,7 @@ export class TrustNetClient {
   async approveCompletion(job: PublicKey, provider: PublicKey): Promise<string> {
     const [vault] = jobVaultPda(job);
     const [treasury] = treasuryPda();
+    const reputations = await this.settlementReputations(job, this.wallet.publicKey, provider);
     return this.program.methods
       .approveCompletion()
       .accounts({
@@ -198,21 +526,60 @@ export class TrustNetClient {
         jobVault: vault,
         treasury,
         provider,
+        ...reputations,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * Approve a submitted milestone (client only). Releases that milestone's escrow;
+   * the provider stake is returned once every milestone has settled.
+   * @param job - Job PDA
+   * @param provider - Provider pubkey (receives payout)
+   * @param milestoneIndex - Index of the milestone to release
+   */
+  async approveMilestone(job: PublicKey, provider: PublicKey, milestoneIndex: number): Promise<string> {
+    const [vault] = jobVaultPda(job);
+    const [treasury] = treasuryPda();
+    const reputations = await this.settlementReputations(job, this.wallet.publicKey, provider);
+    return this.program.methods
+      .approveMilestone(milestoneIndex)
+      .accounts({
+        client: this.wallet.publicKey,
+        job,
+        jobVault: vault,
+        treasury,
+        provider,
+        ...reputations,
         systemProgram: web3.SystemProgram.programId,
       })
       .rpc();
   }
 
   /**
-   * Oracle verification of job completion.
+   * Oracle verification of job completion. A rejection follows the job's configured
+   * reject action: rework, refund, or an automatic dispute.
    * @param job - Job PDA
    * @param provider - Provider pubkey (receives payout if approved)
+   * @param client - Client pubkey (receives refund if a rejection fails the job)
    * @param approved - Whether oracle approves the work
    * @param notesHash - Hash of oracle notes
+   * @param openDispute - Whether the rejection opens a dispute (reject action `Dispute`)
    */
-  async oracleVerify(job: PublicKey, provider: PublicKey, approved: boolean, notesHash: Uint8Array): Promise<string> {
+  async oracleVerify(
+    job: PublicKey,
+    provider: PublicKey,
+    client: PublicKey,
+    approved: boolean,
+    notesHash: Uint8Array,
+    openDispute = false
+  ): Promise<string> {
     const [vault] = jobVaultPda(job);
     const [treasury] = treasuryPda();
+    const [dispute] = disputePda(job);
+    const [disputeVault] = disputeVaultPda(dispute);
+    const reputations = await this.settlementReputations(job, client, provider);
     return this.program.methods
       .oracleVerify(approved, Array.from(notesHash))
       .accounts({
@@ -221,6 +588,221 @@ export class TrustNetClient {
         jobVault: vault,
         treasury,
         provider,
+        client,
+        ...reputations,
+        dispute: openDispute ? dispute : null,
+        disputeVault: openDispute ? disputeVault : null,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * Set the M-of-N oracle committee for an OracleThreshold job (client only, before acceptance).
+   */
+  async setOracleCommittee(job: PublicKey, oracles: PublicKey[], threshold: number): Promise<PublicKey> {
+    const [committee] = oracleCommitteePda(job);
+    await this.program.methods
+      .setOracleCommittee(oracles, threshold)
+      .accounts({
+        client: this.wallet.publicKey,
+        job,
+        committee,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    return committee;
+  }
+
+  /**
+   * Record this oracle's attestation on an OracleThreshold job. The attestation that reaches
+   * M approvals pays out; the one that makes M unreachable applies the job's reject action.
+   * @param job - Job PDA
+   * @param provider - Provider pubkey (receives payout if approved)
+   * @param client - Client pubkey (receives refund if a rejection fails the job)
+   * @param approved - Whether this oracle approves the work
+   * @param notesHash - Hash of oracle notes
+   * @param openDispute - Whether this attestation settles the round into a dispute
+   */
+  async attestOracle(
+    job: PublicKey,
+    provider: PublicKey,
+    client: PublicKey,
+    approved: boolean,
+    notesHash: Uint8Array,
+    openDispute = false
+  ): Promise<PublicKey> {
+    const [committee] = oracleCommitteePda(job);
+    // Note: requires IDL with account definitions. Cast to any for stub IDL.
+    const committeeData = await (this.program.account as any).oracleCommittee.fetch(committee);
+    const [attestation] = oracleAttestationPda(job, this.wallet.publicKey, committeeData.round);
+    const [vault] = jobVaultPda(job);
+    const [treasury] = treasuryPda();
+    const [dispute] = disputePda(job);
+    const [disputeVault] = disputeVaultPda(dispute);
+    const reputations = await this.settlementReputations(job, client, provider);
+    await this.program.methods
+      .attestOracle(approved, Array.from(notesHash))
+      .accounts({
+        oracle: this.wallet.publicKey,
+        job,
+        committee,
+        attestation,
+        jobVault: vault,
+        treasury,
+        provider,
+        client,
+        ...reputations,
+        dispute: openDispute ? dispute : null,
+        disputeVault: openDispute ? disputeVault : null,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    return attestation;
+  }
+
+  /**
+   * Set the reviewer panel for a PeerReview job (client only, before acceptance).
+   * Pass reviewers to name the panel, or an empty list to open `seats` seats to
+   * reputation holders with `specialization`.
+   */
+  async setReviewPanel(
+    job: PublicKey,
+    reviewers: PublicKey[],
+    seats: number,
+    specialization: number | null,
+    bondLamports: bigint
+  ): Promise<PublicKey> {
+    const [panel] = reviewPanelPda(job);
+    const [reviewVault] = reviewVaultPda(job);
+    await this.program.methods
+      .setReviewPanel(reviewers, seats, specialization, new BN(bondLamports.toString()))
+      .accounts({
+        client: this.wallet.publicKey,
+        job,
+        panel,
+        reviewVault,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    return panel;
+  }
+
+  /**
+   * Score a PeerReview submission (1-5, 3+ approves), posting the panel bond.
+   * The review that reaches a majority settles the job.
+   * @param job - Job PDA
+   * @param client - Client pubkey (receives refund if the panel rejects)
+   * @param provider - Provider pubkey (receives payout if the panel approves)
+   */
+  async submitReview(
+    job: PublicKey,
+    client: PublicKey,
+    provider: PublicKey,
+    score: number,
+    commentHash: Uint8Array
+  ): Promise<PublicKey> {
+    const [panel] = reviewPanelPda(job);
+    const [review] = reviewPda(job, this.wallet.publicKey);
+    const [reputation] = reputationPda(this.wallet.publicKey);
+    const reviewerReputation = (await this.connection.getAccountInfo(reputation)) ? reputation : null;
+    const [providerReputation] = reputationPda(provider);
+    const [clientReputation] = reputationPda(client);
+    const { category } = await this.getJob(job);
+    const providerCategoryReputation = category === null ? null : categoryReputationPda(provider, category)[0];
+    const [reviewVault] = reviewVaultPda(job);
+    const [jobVault] = jobVaultPda(job);
+    await this.program.methods
+      .submitReview(score, Array.from(commentHash))
+      .accounts({
+        reviewer: this.wallet.publicKey,
+        job,
+        panel,
+        review,
+        reviewerReputation,
+        providerReputation,
+        clientReputation,
+        providerCategoryReputation,
+        reviewVault,
+        jobVault,
+        provider,
+        client,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    return review;
+  }
+
+  async claimReviewReward(job: PublicKey): Promise<string> {
+    const [panel] = reviewPanelPda(job);
+    const [review] = reviewPda(job, this.wallet.publicKey);
+    const [reviewVault] = reviewVaultPda(job);
+    return this.program.methods
+      .claimReviewReward()
+      .accounts({
+        reviewer: this.wallet.publicKey,
+        job,
+        panel,
+        review,
+        reviewVault,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * Register a Groth16 verifying key. Returns its hash, which a ZkVerify job puts in
+   * `verificationData[0..32]`.
+   */
+  async registerVerifyingKey(vk: Groth16VerifyingKey): Promise<Uint8Array> {
+    const hash = verifyingKeyHash(vk.alphaG1, vk.betaG2, vk.gammaG2, vk.deltaG2, vk.ic);
+    const [verifyingKey] = verifyingKeyPda(hash);
+    await this.program.methods
+      .registerVerifyingKey(
+        Array.from(vk.alphaG1),
+        Array.from(vk.betaG2),
+        Array.from(vk.gammaG2),
+        Array.from(vk.deltaG2),
+        vk.ic.map((p) => Array.from(p))
+      )
+      .accounts({
+        payer: this.wallet.publicKey,
+        verifyingKey,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    return hash;
+  }
+
+  /**
+   * Submit a Groth16 proof for a ZkVerify job (provider only). Releases the escrow if it verifies.
+   * @param job - Job PDA
+   * @param vkHash - Verifying key hash the job committed to
+   * @param publicInputs - Must start with `zkJobBinding(job, verificationData[32..64])`
+   */
+  async submitZkProof(
+    job: PublicKey,
+    vkHash: Uint8Array,
+    proof: Groth16Proof,
+    publicInputs: Uint8Array[]
+  ): Promise<string> {
+    const [verifyingKey] = verifyingKeyPda(vkHash);
+    const [jobVault] = jobVaultPda(job);
+    const [treasury] = treasuryPda();
+    const { client } = await this.getJob(job);
+    const reputations = await this.settlementReputations(job, client, this.wallet.publicKey);
+    return this.program.methods
+      .submitZkProof(
+        { a: Array.from(proof.a), b: Array.from(proof.b), c: Array.from(proof.c) },
+        publicInputs.map((i) => Array.from(i))
+      )
+      .accounts({
+        provider: this.wallet.publicKey,
+        job,
+        verifyingKey,
+        ...reputations,
+        jobVault,
+        treasury,
         systemProgram: web3.SystemProgram.programId,
       })
       .rpc();
@@ -230,11 +812,13 @@ export class TrustNetClient {
    * Expire a job (permissionless after deadline).
    * @param job - Job PDA
    * @param client - Client pubkey (receives refund if job was active)
-   * @param provider - Provider pubkey (receives payout if DeadlineAuto + submitted)
+   * @param provider - Provider pubkey (receives payout if DeadlineAuto + submitted; slashed if the job was active)
    */
   async expireJob(job: PublicKey, client: PublicKey, provider: PublicKey): Promise<string> {
     const [vault] = jobVaultPda(job);
     const [treasury] = treasuryPda();
+    const [providerRepVault] = repVaultPda(provider);
+    const reputations = await this.settlementReputations(job, client, provider);
     return this.program.methods
       .expireJob()
       .accounts({
@@ -243,8 +827,34 @@ export class TrustNetClient {
         treasury,
         client,
         provider,
+        ...reputations,
+        providerRepVault,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /**
+   * Cancel a job and refund escrow. An active job also needs the provider's signature.
+   * @param job - Job PDA
+   * @param provider - Provider pubkey (receives stake back if job was active)
+   * @param providerSigner - Provider keypair; required to cancel an active job
+   */
+  async cancelJob(job: PublicKey, provider: PublicKey, providerSigner?: web3.Keypair): Promise<string> {
+    const [vault] = jobVaultPda(job);
+    const { providerHasReputation } = await this.getJob(job);
+    const providerReputation = providerHasReputation ? reputationPda(provider)[0] : null;
+    return this.program.methods
+      .cancelJob()
+      .accounts({
+        client: this.wallet.publicKey,
+        provider,
+        job,
+        providerReputation,
+        jobVault: vault,
         systemProgram: web3.SystemProgram.programId,
       })
+      .signers(providerSigner ? [providerSigner] : [])
       .rpc();
   }
 
@@ -270,6 +880,63 @@ export class TrustNetClient {
     return dispute;
   }
 
+  async raiseMilestoneDispute(
+    job: PublicKey,
+    milestoneIndex: number,
+    reasonBytes: Uint8Array,
+    evidenceHash: Uint8Array
+  ): Promise<PublicKey> {
+    const [dispute] = milestoneDisputePda(job, milestoneIndex);
+    const [jobVault] = jobVaultPda(job);
+    const [disputeVault] = disputeVaultPda(dispute);
+    await this.program.methods
+      .raiseMilestoneDispute(milestoneIndex, Array.from(reasonBytes), Array.from(evidenceHash))
+      .accounts({
+        raiser: this.wallet.publicKey,
+        job,
+        dispute,
+        jobVault,
+        disputeVault,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    return dispute;
+  }
+
+  /**
+   * Draw a dispute's arbiter panel from the registry and open voting. Permissionless.
+   * Every registry page is passed, in index order, as a remaining account. Waits until
+   * the dispute's `selectionSlot`, whose hash seeds the draw, has passed.
+   * @returns The drawn arbiter accounts
+   */
+  async selectArbiters(dispute: PublicKey): Promise<PublicKey[]> {
+    const disputeData = await (this.program.account as any).dispute.fetch(dispute);
+    while ((await this.connection.getSlot()) <= disputeData.selectionSlot.toNumber()) {
+      await new Promise((resolve) => setTimeout(resolve, 400));
+    }
+    const [registry] = arbiterRegistryPda();
+    const registryData = await (this.program.account as any).arbiterRegistry.fetch(registry);
+    await this.program.methods
+      .selectArbiters()
+      .accounts({
+        dispute,
+        job: disputeData.job,
+        registry,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .remainingAccounts(
+        Array.from({ length: registryData.pageCount }, (_, index) => ({
+          pubkey: arbiterRegistryPagePda(index)[0],
+          isSigner: false,
+          isWritable: false,
+        }))
+      )
+      .rpc();
+    const selected = await (this.program.account as any).dispute.fetch(dispute);
+    return selected.selectedArbiters as PublicKey[];
+  }
+
+  /** Commit a sealed vote. Only arbiters drawn by `selectArbiters` may vote. */
   async commitVote(dispute: PublicKey, vote: boolean, salt: Uint8Array): Promise<string> {
     const [arbiter] = arbiterPda(this.wallet.publicKey);
     const [commitment] = voteCommitmentPda(dispute, arbiter);
@@ -286,6 +953,7 @@ export class TrustNetClient {
       .rpc();
   }
 
+  /** Reveal a committed vote. Reveals open between the commit deadline and the reveal deadline. */
   async revealVote(dispute: PublicKey, vote: boolean, salt: Uint8Array): Promise<string> {
     const [arbiter] = arbiterPda(this.wallet.publicKey);
     const [commitment] = voteCommitmentPda(dispute, arbiter);
@@ -301,17 +969,20 @@ export class TrustNetClient {
   }
 
   /**
-   * Resolve a dispute after voting. Permissionless.
+   * Resolve a dispute once the reveal deadline has passed or every drawn arbiter has revealed.
+   * Permissionless. If the client wins, part of the
+   * provider's reputation stake is slashed to the client and the treasury.
    * @param dispute - Dispute PDA
    * @param client - Client pubkey (receives payout if they win)
    * @param provider - Provider pubkey (receives payout if they win)
-   * @param arbiterAccounts - Array of arbiter remaining accounts (arbiter, arbiterVault, voteCommitment, authority) per arbiter
+   *
+   * Every drawn arbiter that committed a vote is passed, as the program requires, with its
+   * vault, vote, authority wallet and registry page.
    */
   async resolveDispute(
     dispute: PublicKey,
     client: PublicKey,
-    provider: PublicKey,
-    arbiterAccounts: { arbiter: PublicKey; arbiterVault: PublicKey; voteCommitment: PublicKey; authority: PublicKey }[] = []
+    provider: PublicKey
   ): Promise<string> {
     // Note: requires IDL with account definitions. Cast to any for stub IDL.
     const disputeData = await (this.program.account as any).dispute.fetch(dispute);
@@ -319,13 +990,25 @@ export class TrustNetClient {
     const [disputeVault] = disputeVaultPda(dispute);
     const [jobVault] = jobVaultPda(job);
     const [treasury] = treasuryPda();
+    const reputations = await this.settlementReputations(job, client, provider);
+    const [providerRepVault] = repVaultPda(provider);
 
-    const remainingAccounts = arbiterAccounts.flatMap((a) => [
-      { pubkey: a.arbiter, isSigner: false, isWritable: true },
-      { pubkey: a.arbiterVault, isSigner: false, isWritable: true },
-      { pubkey: a.voteCommitment, isSigner: false, isWritable: false },
-      { pubkey: a.authority, isSigner: false, isWritable: true },
-    ]);
+    const [registry] = arbiterRegistryPda();
+    const remainingAccounts: web3.AccountMeta[] = [];
+    for (const arbiter of disputeData.selectedArbiters as PublicKey[]) {
+      const [voteCommitment] = voteCommitmentPda(dispute, arbiter);
+      if (!(await this.connection.getAccountInfo(voteCommitment))) {
+        continue;
+      }
+      const { authority, registryPage } = await (this.program.account as any).arbiter.fetch(arbiter);
+      remainingAccounts.push(
+        { pubkey: arbiter, isSigner: false, isWritable: true },
+        { pubkey: arbiterVaultPda(authority)[0], isSigner: false, isWritable: true },
+        { pubkey: voteCommitment, isSigner: false, isWritable: false },
+        { pubkey: authority, isSigner: false, isWritable: true },
+        { pubkey: arbiterRegistryPagePda(registryPage)[0], isSigner: false, isWritable: true }
+      );
+    }
 
     return this.program.methods
       .resolveDispute()
@@ -337,6 +1020,9 @@ export class TrustNetClient {
         treasury,
         client,
         provider,
+        ...reputations,
+        providerRepVault,
+        registry,
         systemProgram: web3.SystemProgram.programId,
       })
       .remainingAccounts(remainingAccounts)
@@ -347,15 +1033,27 @@ export class TrustNetClient {
   // Ratings
   // ─────────────────────────────────────────────────────────────────────────────
 
+  /**
+   * Rate the counterparty of a completed or resolved job. The rating is weighted by the
+   * job's value and, if this wallet has a reputation profile, by its own score.
+   */
   async rateJob(job: PublicKey, args: RateJobArgs): Promise<string> {
     const [rating] = ratingPda(args.jobId, this.wallet.publicKey);
     const [rateeReputation] = reputationPda(args.ratee);
+    const [reputation] = reputationPda(this.wallet.publicKey);
+    const raterReputation = (await this.connection.getAccountInfo(reputation)) ? reputation : null;
+    const { category } = await this.getJob(job);
+    const categoryTrack = category === null ? null : categoryReputationPda(args.ratee, category)[0];
+    const rateeCategoryReputation =
+      categoryTrack && (await this.connection.getAccountInfo(categoryTrack)) ? categoryTrack : null;
     return this.program.methods
       .rateJob(Array.from(args.jobId), args.score, Array.from(args.tags), Array.from(args.commentHash))
       .accounts({
         rater: this.wallet.publicKey,
         job,
         rateeReputation,
+        raterReputation,
+        rateeCategoryReputation,
         rating,
         systemProgram: web3.SystemProgram.programId,
       })
@@ -366,21 +1064,117 @@ export class TrustNetClient {
   // Arbiters
   // ─────────────────────────────────────────────────────────────────────────────
 
+  /** Create the arbiter registry dispute panels are drawn from. Once per deployment. */
+  async initArbiterRegistry(): Promise<PublicKey> {
+    const [registry] = arbiterRegistryPda();
+    await this.program.methods
+      .initArbiterRegistry()
+      .accounts({
+        payer: this.wallet.publicKey,
+        registry,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    return registry;
+  }
+
+  /** Append the next registry page. Only succeeds once every existing page is full. */
+  async addArbiterRegistryPage(): Promise<PublicKey> {
+    const [registry] = arbiterRegistryPda();
+    const registryData = await (this.program.account as any).arbiterRegistry.fetch(registry);
+    const [page] = arbiterRegistryPagePda(registryData.pageCount);
+    await this.program.methods
+      .addArbiterRegistryPage()
+      .accounts({
+        payer: this.wallet.publicKey,
+        registry,
+        page,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    return page;
+  }
+
+  /** Register as an arbiter; the entry goes on the first registry page with room, adding a page if needed. */
   async registerArbiter(stakeLamports: bigint, specializations: number[] = []): Promise<PublicKey> {
     const [arbiter] = arbiterPda(this.wallet.publicKey);
     const [arbiterVault] = arbiterVaultPda(this.wallet.publicKey);
+    const [registry] = arbiterRegistryPda();
+    const registryPage = (await this.registryPageWithRoom()) ?? (await this.addArbiterRegistryPage());
     await this.program.methods
       .registerArbiter(new BN(stakeLamports.toString()), specializations)
       .accounts({
         authority: this.wallet.publicKey,
         arbiter,
         arbiterVault,
+        registry,
+        registryPage,
         systemProgram: web3.SystemProgram.programId,
       })
       .rpc();
     return arbiter;
   }
 
+  /** Top up arbiter stake. A deactivated arbiter back at the minimum re-enters the registry. */
+  async addArbiterStake(amountLamports: bigint): Promise<void> {
+    const [arbiter] = arbiterPda(this.wallet.publicKey);
+    const arbiterData = await (this.program.account as any).arbiter.fetch(arbiter);
+    const registryPage = arbiterData.active
+      ? arbiterRegistryPagePda(arbiterData.registryPage)[0]
+      : (await this.registryPageWithRoom()) ?? (await this.addArbiterRegistryPage());
+    await this.program.methods
+      .addArbiterStake(new BN(amountLamports.toString()))
+      .accounts({
+        authority: this.wallet.publicKey,
+        arbiter,
+        arbiterVault: arbiterVaultPda(this.wallet.publicKey)[0],
+        registry: arbiterRegistryPda()[0],
+        registryPage,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  /** Leave the registry and start the withdrawal cooldown. */
+  async deactivateArbiter(): Promise<void> {
+    const [arbiter] = arbiterPda(this.wallet.publicKey);
+    const arbiterData = await (this.program.account as any).arbiter.fetch(arbiter);
+    await this.program.methods
+      .deactivateArbiter()
+      .accounts({
+        authority: this.wallet.publicKey,
+        arbiter,
+        registry: arbiterRegistryPda()[0],
+        registryPage: arbiterRegistryPagePda(arbiterData.registryPage)[0],
+      })
+      .rpc();
+  }
+
+  /** Withdraw a deactivated arbiter's stake after the cooldown, once its disputes are resolved. */
+  async withdrawArbiterStake(): Promise<void> {
+    await this.program.methods
+      .withdrawArbiterStake()
+      .accounts({
+        authority: this.wallet.publicKey,
+        arbiter: arbiterPda(this.wallet.publicKey)[0],
+        arbiterVault: arbiterVaultPda(this.wallet.publicKey)[0],
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  private async registryPageWithRoom(): Promise<PublicKey | null> {
+    const registryData = await (this.program.account as any).arbiterRegistry.fetch(arbiterRegistryPda()[0]);
+    for (let index = 0; index < registryData.pageCount; index++) {
+      const [page] = arbiterRegistryPagePda(index);
+      const pageData = await (this.program.account as any).arbiterRegistryPage.fetch(page);
+      if (pageData.entries.length < ARBITER_REGISTRY_PAGE_SIZE) {
+        return page;
+      }
+    }
+    return null;
+  }
+
   // ─────────────────────────────────────────────────────────────────────────────
   // Helpers
   // ─────────────────────────────────────────────────────────────────────────────
@@ -397,6 +1191,8 @@ export class TrustNetClient {
         return { peerReview: {} };
       case "ZkVerify":
         return { zkVerify: {} };
+      case "OracleThreshold":
+        return { oracleThreshold: {} };
     }
   }
 }
@@ -5,6 +5,33 @@ export function sha256(data: Uint8Array): Uint8Array {
   return crypto.createHash("sha256").update(Buffer.from(data)).digest();
 }
 
+export function verifyingKeyHash(
+  alphaG1: Uint8Array,
+  betaG2: Uint8Array,
+  gammaG2: Uint8Array,
+  deltaG2: Uint8Array,
+  ic: Uint8Array[]
+): Uint8Array {
+  return sha256(Buffer.concat([alphaG1, betaG2, gammaG2, deltaG2, ...ic].map((b) => Buffer.from(b))));
+}
+
+/**
+ * First public input of every ZkVerify proof: sha256(job || verificationData[32..64]) with the
+ * top byte cleared, so a proof is bound to one job and the client's expected output.
+ */
+export function zkJobBinding(job: PublicKey, expectedOutput: Uint8Array): Uint8Array {
+  const binding = sha256(Buffer.concat([job.toBuffer(), Buffer.from(expectedOutput)]));
+  binding[0] = 0;
+  return binding;
+}
+
+export function bidHash(bidder: PublicKey, job: PublicKey, amount: bigint, salt: Uint8Array): Uint8Array {
+  const amountLe = Buffer.alloc(8);
+  amountLe.writeBigUInt64LE(amount);
+  const data = Buffer.concat([bidder.toBuffer(), job.toBuffer(), amountLe, Buffer.from(salt)]);
+  return sha256(data);
+}
+
 export function voteHash(arbiter: PublicKey, dispute: PublicKey, vote: boolean, salt: Uint8Array): Uint8Array {
   const data = Buffer.concat([
     arbiter.toBuffer(),
@@ -10,6 +10,48 @@ export function jobVaultPda(job: PublicKey, programId: PublicKey = PROGRAM_ID):
   return PublicKey.findProgramAddressSync([Buffer.from("job_vault"), job.toBuffer()], programId);
 }
 
+export function jobTokenVaultPda(job: PublicKey, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("job_token_vault"), job.toBuffer()], programId);
+}
+
+export function bidPda(job: PublicKey, bidder: PublicKey, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("bid"), job.toBuffer(), bidder.toBuffer()], programId);
+}
+
+export function oracleCommitteePda(job: PublicKey, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("oracle_committee"), job.toBuffer()], programId);
+}
+
+export function oracleAttestationPda(
+  job: PublicKey,
+  oracle: PublicKey,
+  round: number,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  const roundLe = Buffer.alloc(2);
+  roundLe.writeUInt16LE(round);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("attestation"), job.toBuffer(), oracle.toBuffer(), roundLe],
+    programId
+  );
+}
+
+export function reviewPanelPda(job: PublicKey, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("review_panel"), job.toBuffer()], programId);
+}
+
+export function reviewVaultPda(job: PublicKey, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("review_vault"), job.toBuffer()], programId);
+}
+
+export function reviewPda(job: PublicKey, reviewer: PublicKey, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("review"), job.toBuffer(), reviewer.toBuffer()], programId);
+}
+
+export function verifyingKeyPda(vkHash: Uint8Array, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("zk_vk"), Buffer.from(vkHash)], programId);
+}
+
 export function treasuryPda(programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
   return PublicKey.findProgramAddressSync([Buffer.from("treasury")], programId);
 }
@@ -18,6 +60,17 @@ export function reputationPda(agent: PublicKey, programId: PublicKey = PROGRAM_I
   return PublicKey.findProgramAddressSync([Buffer.from("reputation"), agent.toBuffer()], programId);
 }
 
+export function categoryReputationPda(
+  agent: PublicKey,
+  category: number,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("category_rep"), agent.toBuffer(), Buffer.from([category])],
+    programId
+  );
+}
+
 export function repVaultPda(agent: PublicKey, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
   return PublicKey.findProgramAddressSync([Buffer.from("rep_vault"), agent.toBuffer()], programId);
 }
@@ -26,6 +79,16 @@ export function arbiterPda(authority: PublicKey, programId: PublicKey = PROGRAM_
   return PublicKey.findProgramAddressSync([Buffer.from("arbiter"), authority.toBuffer()], programId);
 }
 
+export function arbiterRegistryPda(programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("arbiter_registry")], programId);
+}
+
+export function arbiterRegistryPagePda(index: number, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  const indexLe = Buffer.alloc(4);
+  indexLe.writeUInt32LE(index);
+  return PublicKey.findProgramAddressSync([Buffer.from("arbiter_registry_page"), indexLe], programId);
+}
+
 export function arbiterVaultPda(authority: PublicKey, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
   return PublicKey.findProgramAddressSync([Buffer.from("arbiter_vault"), authority.toBuffer()], programId);
 }
@@ -34,10 +97,27 @@ export function disputePda(job: PublicKey, programId: PublicKey = PROGRAM_ID): [
   return PublicKey.findProgramAddressSync([Buffer.from("dispute"), job.toBuffer()], programId);
 }
 
+export function submissionPda(job: PublicKey, index: number, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  const indexLe = Buffer.alloc(2);
+  indexLe.writeUInt16LE(index);
+  return PublicKey.findProgramAddressSync([Buffer.from("submission"), job.toBuffer(), indexLe], programId);
+}
+
+export function milestoneDisputePda(job: PublicKey, milestoneIndex: number, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("dispute"), job.toBuffer(), Buffer.from([milestoneIndex])],
+    programId
+  );
+}
+
 export function disputeVaultPda(dispute: PublicKey, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
   return PublicKey.findProgramAddressSync([Buffer.from("dispute_vault"), dispute.toBuffer()], programId);
 }
 
+export function disputeTokenVaultPda(dispute: PublicKey, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("dispute_token_vault"), dispute.toBuffer()], programId);
+}
+
 export function ratingPda(jobId: Uint8Array, rater: PublicKey, programId: PublicKey = PROGRAM_ID): [PublicKey, number] {
   return PublicKey.findProgramAddressSync([Buffer.from("rating"), Buffer.from(jobId), rater.toBuffer()], programId);
 }
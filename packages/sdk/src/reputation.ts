@@ -1,22 +1,64 @@
 import { AgentReputationView } from "./types";
 
+/** Mirrors `REPUTATION_HALF_LIFE_SECONDS` in the program. */
+export const REPUTATION_HALF_LIFE_SECONDS = 730 * 86_400;
+
+/** Mirror `RATING_PRIOR_MEAN` / `RATING_PRIOR_WEIGHT` in the program. */
+export const RATING_PRIOR_MEAN = 300;
+export const RATING_PRIOR_WEIGHT = 10;
+
+const BPS_DENOMINATOR = 10_000n;
+
+// 2^(-1/2^(k+1)) in Q32 for k = 0..15; must match `HALF_LIFE_ROOTS_Q32` in utils/math.rs.
+const HALF_LIFE_ROOTS_Q32: bigint[] = [
+  3_037_000_500n, 3_611_622_603n, 3_938_502_376n, 4_112_874_773n,
+  4_202_935_003n, 4_248_701_965n, 4_271_771_996n, 4_283_353_945n,
+  4_289_156_690n, 4_292_061_010n, 4_293_513_907n, 4_294_240_540n,
+  4_294_603_903n, 4_294_785_595n, 4_294_876_445n, 4_294_921_870n,
+];
+
+/**
+ * Continuous half-life decay in basis points, bit-for-bit identical to the on-chain
+ * `decay_multiplier_bps`.
+ */
+export function decayMultiplierBps(inactiveSecs: number, halfLifeSecs: number = REPUTATION_HALF_LIFE_SECONDS): number {
+  if (inactiveSecs <= 0 || halfLifeSecs <= 0) return Number(BPS_DENOMINATOR);
+  const elapsed = BigInt(Math.floor(inactiveSecs));
+  const halfLife = BigInt(Math.floor(halfLifeSecs));
+  const halvings = elapsed / halfLife;
+  if (halvings >= 32n) return 0;
+  const frac = ((elapsed % halfLife) * 65_536n) / halfLife;
+  let multiplier = 1n << 32n;
+  HALF_LIFE_ROOTS_Q32.forEach((root, k) => {
+    if (frac & (1n << BigInt(15 - k))) multiplier = (multiplier * root) >> 32n;
+  });
+  multiplier >>= halvings;
+  return Number((multiplier * BPS_DENOMINATOR) >> 32n);
+}
+
+/** Bayesian average rating (scaled by 100), identical to the on-chain `bayesian_rating`. */
+export function bayesianRating(ratingSum: bigint, ratingCount: bigint): number {
+  const prior = BigInt(RATING_PRIOR_MEAN * RATING_PRIOR_WEIGHT);
+  return Number((prior + ratingSum) / (BigInt(RATING_PRIOR_WEIGHT) + ratingCount));
+}
+
+/** Off-chain port of the program's reputation score; integer math throughout. */
 export function computeScore(view: AgentReputationView, nowTs: number): number {
-  const completed = Number(view.totalJobsCompleted);
-  const failed = Number(view.totalJobsFailed);
-  const disputesWon = Number(view.totalDisputesWon);
-  const disputesLost = Number(view.totalDisputesLost);
+  const completed = view.totalJobsCompleted;
+  const failed = view.totalJobsFailed;
   const total = completed + failed;
-  let base = total === 0 ? 0 : Math.floor((completed / total) * 100);
-  base += disputesWon * 2 - disputesLost * 5;
-  if (completed >= 50) base += 15;
-  else if (completed >= 20) base += 10;
-  else if (completed >= 5) base += 5;
-  const stakeSol = Number(view.stakeAmount) / 1_000_000_000;
-  if (stakeSol >= 50) base += 10;
-  else if (stakeSol >= 10) base += 5;
-  else if (stakeSol >= 1) base += 2;
-  const daysInactive = Math.floor((nowTs - view.lastActive) / 86_400);
-  const multiplier = daysInactive <= 30 ? 1 : daysInactive <= 90 ? 0.95 : daysInactive <= 180 ? 0.9 : daysInactive <= 360 ? 0.8 : 0.7;
-  const adjusted = Math.floor(base * multiplier);
+  let base = total === 0n ? 0n : (completed * 100n) / total;
+  base += view.totalDisputesWon * 2n - view.totalDisputesLost * 5n;
+  if (completed >= 50n) base += 15n;
+  else if (completed >= 20n) base += 10n;
+  else if (completed >= 5n) base += 5n;
+  const stakeSol = view.stakeAmount / 1_000_000_000n;
+  if (stakeSol >= 50n) base += 10n;
+  else if (stakeSol >= 10n) base += 5n;
+  else if (stakeSol >= 1n) base += 2n;
+  base += BigInt(view.bayesianRating - RATING_PRIOR_MEAN) / 20n;
+  const multiplier = BigInt(decayMultiplierBps(nowTs - view.lastActive));
+  // BigInt division truncates toward zero, like Rust's i64 division.
+  const adjusted = Number((base * multiplier) / BPS_DENOMINATOR);
   return Math.max(0, Math.min(100, adjusted));
 }
@@ -1,8 +1,11 @@
 import { PublicKey } from "@solana/web3.js";
 
-export type VerifyType = "ClientApproval" | "OracleVerify" | "DeadlineAuto" | "PeerReview" | "ZkVerify";
+export type VerifyType = "ClientApproval" | "OracleVerify" | "DeadlineAuto" | "PeerReview" | "ZkVerify" | "OracleThreshold";
 
-export type JobStatus = "Created" | "Active" | "Submitted" | "Completed" | "Disputed" | "Resolved" | "Cancelled" | "Expired";
+export type JobStatus = "Created" | "Active" | "Submitted" | "Completed" | "Disputed" | "Resolved" | "Cancelled" | "Expired" | "Failed";
+
+/** Outcome of an oracle rejection, encoded in `verificationData[32]` (0, 1, 2). */
+export type OracleRejectAction = "Rework" | "Refund" | "Dispute";
 
 export interface CreateJobArgs {
   jobId: Uint8Array;
@@ -11,7 +14,59 @@ export interface CreateJobArgs {
   verificationType: VerifyType;
   verificationData: Uint8Array;
   termsHash: Uint8Array;
+  /** Omit to create an open job that any eligible provider can accept. */
+  provider?: PublicKey;
+  /** SPL / Token-2022 mint; omit for a lamport-denominated job. */
+  mint?: PublicKey;
+  clientTokenAccount?: PublicKey;
+  tokenProgram?: PublicKey;
+}
+
+export interface ProviderRequirements {
+  minReputationScore: number;
+  minStake: bigint;
+  specializations: number[];
+}
+
+export type MilestoneStatus = "Pending" | "Submitted" | "Disputed" | "Released" | "Refunded";
+
+export interface MilestoneSpec {
+  amount: bigint;
+  deadline: number;
+  termsHash: Uint8Array;
+}
+
+export interface MilestoneView {
+  amount: bigint;
+  deadline: number;
+  termsHash: Uint8Array;
+  status: MilestoneStatus;
+  submittedAt: number | null;
+  submissionIndex: number | null;
+}
+
+export interface SubmissionView {
+  job: PublicKey;
   provider: PublicKey;
+  index: number;
+  milestone: number | null;
+  submissionHash: Uint8Array;
+  submittedAt: number;
+}
+
+/** Groth16 verifying key; points big-endian as the alt_bn128 syscalls expect. */
+export interface Groth16VerifyingKey {
+  alphaG1: Uint8Array; // 64 bytes
+  betaG2: Uint8Array; // 128 bytes
+  gammaG2: Uint8Array; // 128 bytes
+  deltaG2: Uint8Array; // 128 bytes
+  ic: Uint8Array[]; // 64 bytes each, one more than the public inputs
+}
+
+export interface Groth16Proof {
+  a: Uint8Array; // 64 bytes
+  b: Uint8Array; // 128 bytes
+  c: Uint8Array; // 64 bytes
 }
 
 export interface RateJobArgs {
@@ -34,6 +89,25 @@ export interface JobView {
   createdAt: number;
   submittedAt: number | null;
   completedAt: number | null;
+  mint: PublicKey | null;
+  revisionCount: number;
+  submissionCount: number;
+  submissionHash: Uint8Array | null;
+  category: number | null;
+  /** Whether the provider accepted with its reputation profile, which settlement must then update. */
+  providerHasReputation: boolean;
+  milestones: MilestoneView[];
+}
+
+export interface ReputationScoreView {
+  score: number;
+  completionRate: number;
+  disputeAdjustment: number;
+  volumeBonus: number;
+  stakeBonus: number;
+  ratingAdjustment: number;
+  decayMultiplier: number;
+  daysInactive: number;
 }
 
 export interface AgentReputationView {
@@ -44,7 +118,31 @@ export interface AgentReputationView {
   totalDisputesLost: bigint;
   totalVolume: bigint;
   avgRating: number;
+  /** Confidence-adjusted (Bayesian) average; pulled toward the protocol prior when ratings are few. */
+  bayesianRating: number;
+  /** Average weighted by job value and rater score; both averages are scaled by 100. */
+  weightedAvgRating: number;
   ratingCount: bigint;
+  /** Active stake; excludes stake that is unbonding. */
   stakeAmount: bigint;
+  unbondingAmount: bigint;
+  /** Unix time after which `unbondingAmount` can be withdrawn. */
+  unbondingReadyAt: number;
+  closed: boolean;
+  /** Jobs accepted with this profile and not yet settled; stake cannot unbond while any are open. */
+  openJobs: number;
+  lastActive: number;
+}
+
+/** An agent's record within one job category; global counters still aggregate all categories. */
+export interface CategoryReputationView {
+  agent: PublicKey;
+  category: number;
+  jobsCompleted: bigint;
+  jobsFailed: bigint;
+  volume: bigint;
+  avgRating: number;
+  bayesianRating: number;
+  ratingCount: bigint;
   lastActive: number;
 }
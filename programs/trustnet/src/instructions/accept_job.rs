@@ -1,21 +1,64 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::job_escrow::{JobEscrow, JobStatus};
+use crate::state::job_escrow::{JobEscrow, JobStatus, VerifyType};
+use crate::state::oracle::OracleCommittee;
+use crate::state::reputation::{load_reputation, store_reputation, CategoryReputation};
+use crate::state::review::ReviewPanel;
+use crate::utils::constants::now_ts;
 use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::deposit_tokens;
 
 #[derive(Accounts)]
 pub struct AcceptJob<'info> {
     #[account(mut)]
     pub provider: Signer<'info>,
-    #[account(mut, has_one = provider @ TrustNetError::Unauthorized)]
+    #[account(mut)]
     pub job: Account<'info, JobEscrow>,
+    /// CHECK: the provider's reputation PDA, loaded with `load_reputation` whenever it exists.
+    /// A provider with a profile always accepts with it, and the job counts against the profile's
+    /// stake until settlement. Jobs that set provider requirements need the profile.
+    #[account(
+        mut,
+        seeds = [b"reputation", provider.key().as_ref()],
+        bump
+    )]
+    pub provider_reputation: UncheckedAccount<'info>,
+    /// Required when the job has a category, so that settlement can record it.
+    #[account(
+        seeds = [b"category_rep", provider.key().as_ref(), &[job.category.unwrap_or_default()]],
+        bump
+    )]
+    pub provider_category_reputation: Option<Account<'info, CategoryReputation>>,
+    /// Required for an OracleThreshold job, which can only settle through its committee.
+    #[account(
+        seeds = [b"oracle_committee", job.key().as_ref()],
+        bump
+    )]
+    pub oracle_committee: Option<Account<'info, OracleCommittee>>,
+    /// Required for a PeerReview job, which can only settle through its panel.
+    #[account(
+        seeds = [b"review_panel", job.key().as_ref()],
+        bump
+    )]
+    pub review_panel: Option<Account<'info, ReviewPanel>>,
     #[account(
         mut,
         seeds = [b"job_vault", job.key().as_ref()],
         bump
     )]
     pub job_vault: SystemAccount<'info>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub provider_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"job_token_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
 
@@ -24,16 +67,50 @@ pub fn handler(ctx: Context<AcceptJob>, stake_amount: u64) -> Result<()> {
     require!(job.status == JobStatus::Created, TrustNetError::InvalidStatus);
     require!(stake_amount > 0, TrustNetError::InsufficientFunds);
 
+    let provider = ctx.accounts.provider.key();
+    if job.is_open() {
+        // Auctioned jobs are assigned by awarding a bid, not first come first served.
+        require!(job.auction.is_none(), TrustNetError::InvalidStatus);
+        job.provider = provider;
+    } else {
+        require_keys_eq!(job.provider, provider, TrustNetError::Unauthorized);
+    }
+    let mut reputation = load_reputation(&ctx.accounts.provider_reputation)?;
+    job.check_provider_eligible(reputation.as_ref(), now_ts(&Clock::get()?))?;
+    if job.category.is_some() {
+        require!(ctx.accounts.provider_category_reputation.is_some(), TrustNetError::MissingCategoryReputation);
+    }
+    if job.verification_type == VerifyType::OracleThreshold {
+        require!(ctx.accounts.oracle_committee.is_some(), TrustNetError::InvalidOracleCommittee);
+    }
+    if job.verification_type == VerifyType::PeerReview {
+        require!(ctx.accounts.review_panel.is_some(), TrustNetError::InvalidReviewPanel);
+    }
+
     job.status = JobStatus::Active;
-    job.provider_stake = stake_amount;
+    if let Some(reputation) = reputation.as_mut() {
+        reputation.open_jobs = reputation.open_jobs.saturating_add(1);
+        job.provider_has_reputation = true;
+    }
+    store_reputation(&ctx.accounts.provider_reputation, &reputation)?;
 
-    let cpi = CpiContext::new(
-        ctx.accounts.system_program.to_account_info(),
-        system_program::Transfer {
-            from: ctx.accounts.provider.to_account_info(),
-            to: ctx.accounts.job_vault.to_account_info(),
-        },
-    );
-    system_program::transfer(cpi, stake_amount)?;
+    if let Some(job_mint) = job.mint {
+        let mint = ctx.accounts.mint.as_ref().ok_or(TrustNetError::MissingTokenAccount)?;
+        require_keys_eq!(mint.key(), job_mint, TrustNetError::MintMismatch);
+        let from = ctx.accounts.provider_token_account.as_ref().ok_or(TrustNetError::MissingTokenAccount)?;
+        let vault = ctx.accounts.job_token_vault.as_mut().ok_or(TrustNetError::MissingTokenAccount)?;
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(TrustNetError::MissingTokenAccount)?;
+        job.provider_stake = deposit_tokens(token_program, mint, from, vault, &ctx.accounts.provider, stake_amount)?;
+    } else {
+        job.provider_stake = stake_amount;
+        let cpi = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.provider.to_account_info(),
+                to: ctx.accounts.job_vault.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi, stake_amount)?;
+    }
     Ok(())
 }
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::state::arbiter::{ArbiterRegistry, ArbiterRegistryPage};
+use crate::utils::constants::{ARBITER_REGISTRY_PAGE_SIZE, MAX_ARBITER_REGISTRY_PAGES};
+use crate::utils::errors::TrustNetError;
+
+#[derive(Accounts)]
+pub struct AddArbiterRegistryPage<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"arbiter_registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, ArbiterRegistry>,
+    #[account(
+        init,
+        payer = payer,
+        space = ArbiterRegistryPage::LEN,
+        seeds = [b"arbiter_registry_page", registry.page_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub page: Account<'info, ArbiterRegistryPage>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Appends the next registry page. Permissionless, but only once every existing page is full.
+pub fn handler(ctx: Context<AddArbiterRegistryPage>) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    require!(registry.page_count < MAX_ARBITER_REGISTRY_PAGES, TrustNetError::ArbiterRegistryFull);
+    let capacity = registry.page_count as usize * ARBITER_REGISTRY_PAGE_SIZE;
+    require!(registry.arbiter_count as usize >= capacity, TrustNetError::ArbiterRegistryPageNotFull);
+
+    let page = &mut ctx.accounts.page;
+    page.index = registry.page_count;
+    page.entries = Vec::new();
+    page.total_stake = 0;
+    page.bump = *ctx.bumps.get("page").unwrap();
+    registry.page_count += 1;
+    Ok(())
+}
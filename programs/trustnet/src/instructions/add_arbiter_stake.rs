@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::state::arbiter::{Arbiter, ArbiterRegistry, ArbiterRegistryPage, RegistryEntry};
+use crate::utils::constants::MIN_ARBITER_STAKE_LAMPORTS;
+use crate::utils::errors::TrustNetError;
+
+#[derive(Accounts)]
+pub struct AddArbiterStake<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut, has_one = authority @ TrustNetError::Unauthorized)]
+    pub arbiter: Account<'info, Arbiter>,
+    #[account(
+        mut,
+        seeds = [b"arbiter_vault", authority.key().as_ref()],
+        bump
+    )]
+    pub arbiter_vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"arbiter_registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, ArbiterRegistry>,
+    /// The arbiter's page while active; any page with room to reactivate.
+    #[account(
+        mut,
+        seeds = [b"arbiter_registry_page", registry_page.index.to_le_bytes().as_ref()],
+        bump = registry_page.bump
+    )]
+    pub registry_page: Account<'info, ArbiterRegistryPage>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Tops up an arbiter's stake. A deactivated arbiter that reaches the minimum again
+/// is reactivated and re-enters the registry.
+pub fn handler(ctx: Context<AddArbiterStake>, amount: u64) -> Result<()> {
+    require!(amount > 0, TrustNetError::InsufficientFunds);
+    let cpi = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: ctx.accounts.arbiter_vault.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi, amount)?;
+
+    let arbiter = &mut ctx.accounts.arbiter;
+    arbiter.stake = arbiter.stake.saturating_add(amount);
+    let page = &mut ctx.accounts.registry_page;
+    if arbiter.active {
+        require!(page.index == arbiter.registry_page, TrustNetError::InvalidArbiterSet);
+        page.set_stake(&mut ctx.accounts.registry, &arbiter.key(), arbiter.stake)?;
+    } else {
+        require!(arbiter.stake >= MIN_ARBITER_STAKE_LAMPORTS, TrustNetError::MinimumStakeNotMet);
+        arbiter.active = true;
+        arbiter.withdrawable_at = 0;
+        arbiter.registry_page = page.index;
+        page.insert(&mut ctx.accounts.registry, RegistryEntry::new(arbiter))?;
+    }
+    Ok(())
+}
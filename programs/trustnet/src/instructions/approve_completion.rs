@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::job_escrow::{JobEscrow, JobStatus, VerifyType};
+use crate::state::job_escrow::{JobEscrow, JobStatus, MilestoneStatus, VerifyType};
+use crate::state::reputation::{settle_reputations, CategoryReputation};
 use crate::utils::constants::{BPS_DENOMINATOR, PROTOCOL_FEE_BPS, now_ts};
 use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::{Escrow, TokenVault};
 
 #[derive(Accounts)]
 pub struct ApproveCompletion<'info> {
@@ -23,8 +25,43 @@ pub struct ApproveCompletion<'info> {
     )]
     pub treasury: SystemAccount<'info>,
     /// CHECK: provider receives payout
-    #[account(mut)]
+    #[account(mut, address = job.provider @ TrustNetError::Unauthorized)]
     pub provider: UncheckedAccount<'info>,
+    /// CHECK: the provider's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.provider.as_ref()],
+        bump
+    )]
+    pub provider_reputation: UncheckedAccount<'info>,
+    /// CHECK: the client's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.client.as_ref()],
+        bump
+    )]
+    pub client_reputation: UncheckedAccount<'info>,
+    /// Required when the job has a category.
+    #[account(
+        mut,
+        seeds = [b"category_rep", job.provider.as_ref(), &[job.category.unwrap_or_default()]],
+        bump
+    )]
+    pub provider_category_reputation: Option<Account<'info, CategoryReputation>>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"job_token_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub client_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub provider_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
 
@@ -32,6 +69,7 @@ pub fn handler(ctx: Context<ApproveCompletion>) -> Result<()> {
     let job = &mut ctx.accounts.job;
     require!(job.status == JobStatus::Submitted, TrustNetError::InvalidStatus);
     require!(job.verification_type == VerifyType::ClientApproval, TrustNetError::InvalidStatus);
+    require!(job.milestones.is_empty(), TrustNetError::InvalidMilestone);
 
     let total = job.amount.checked_add(job.provider_stake).ok_or(TrustNetError::InsufficientFunds)?;
     let fee = job.amount
@@ -40,32 +78,100 @@ pub fn handler(ctx: Context<ApproveCompletion>) -> Result<()> {
         .unwrap_or(0);
     let payout = total.saturating_sub(fee);
 
-    let job_key = job.key();
-    let signer_seeds = &[b"job_vault", job_key.as_ref(), &[*ctx.bumps.get("job_vault").unwrap()]];
+    let token = TokenVault::for_mint(
+        job.mint,
+        &ctx.accounts.job_token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+    let escrow = Escrow::new(b"job_vault", job.key(), &ctx.accounts.job_vault, &ctx.accounts.system_program, token);
+    escrow.pay(&ctx.accounts.provider, &ctx.accounts.provider_token_account, payout)?;
+    escrow.pay(&ctx.accounts.treasury, &ctx.accounts.treasury_token_account, fee)?;
 
-    let transfer_to_provider = CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),
-        system_program::Transfer {
-            from: ctx.accounts.job_vault.to_account_info(),
-            to: ctx.accounts.provider.to_account_info(),
-        },
-        &[signer_seeds],
-    );
-    system_program::transfer(transfer_to_provider, payout)?;
+    let now = now_ts(&Clock::get()?);
+    job.status = JobStatus::Completed;
+    job.completed_at = Some(now);
+    settle_reputations(
+        job,
+        &ctx.accounts.provider_reputation,
+        &ctx.accounts.client_reputation,
+        ctx.accounts.provider_category_reputation.as_deref_mut(),
+        true,
+        job.amount,
+        now,
+    )?;
+    Ok(())
+}
 
-    if fee > 0 {
-        let transfer_fee = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.job_vault.to_account_info(),
-                to: ctx.accounts.treasury.to_account_info(),
-            },
-            &[signer_seeds],
-        );
-        system_program::transfer(transfer_fee, fee)?;
+pub fn milestone_handler(ctx: Context<ApproveCompletion>, milestone_index: u8) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Active, TrustNetError::InvalidStatus);
+    require!(job.verification_type == VerifyType::ClientApproval, TrustNetError::InvalidStatus);
+
+    let milestone = job.milestones.get_mut(milestone_index as usize).ok_or(TrustNetError::InvalidMilestone)?;
+    require!(milestone.status == MilestoneStatus::Submitted, TrustNetError::InvalidStatus);
+    milestone.status = MilestoneStatus::Released;
+    let amount = milestone.amount;
+    let fee = amount
+        .checked_mul(PROTOCOL_FEE_BPS)
+        .and_then(|v| v.checked_div(BPS_DENOMINATOR))
+        .unwrap_or(0);
+
+    let token = TokenVault::for_mint(
+        job.mint,
+        &ctx.accounts.job_token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+    let escrow = Escrow::new(b"job_vault", job.key(), &ctx.accounts.job_vault, &ctx.accounts.system_program, token);
+    escrow.pay(&ctx.accounts.provider, &ctx.accounts.provider_token_account, amount.saturating_sub(fee))?;
+    escrow.pay(&ctx.accounts.treasury, &ctx.accounts.treasury_token_account, fee)?;
+
+    let now = now_ts(&Clock::get()?);
+    finish_milestone_job(
+        job,
+        &escrow,
+        &ctx.accounts.client,
+        &ctx.accounts.client_token_account,
+        &ctx.accounts.provider,
+        &ctx.accounts.provider_token_account,
+        now,
+    )?;
+    if job.status == JobStatus::Completed {
+        settle_reputations(
+            job,
+            &ctx.accounts.provider_reputation,
+            &ctx.accounts.client_reputation,
+            ctx.accounts.provider_category_reputation.as_deref_mut(),
+            job.fully_delivered(),
+            job.released_amount(),
+            now,
+        )?;
     }
+    Ok(())
+}
 
+/// Completes a milestone job once every milestone is released or refunded.
+/// The provider stake goes back to the provider only if nothing was refunded.
+pub fn finish_milestone_job<'info>(
+    job: &mut JobEscrow,
+    escrow: &Escrow<'_, 'info>,
+    client: &AccountInfo<'info>,
+    client_token_account: &Option<InterfaceAccount<'info, TokenAccount>>,
+    provider: &AccountInfo<'info>,
+    provider_token_account: &Option<InterfaceAccount<'info, TokenAccount>>,
+    now: i64,
+) -> Result<()> {
+    if !job.milestones_settled() {
+        return Ok(());
+    }
+    let refunded = job.milestones.iter().any(|m| m.status == MilestoneStatus::Refunded);
+    if refunded {
+        escrow.pay(client, client_token_account, job.provider_stake)?;
+    } else {
+        escrow.pay(provider, provider_token_account, job.provider_stake)?;
+    }
     job.status = JobStatus::Completed;
-    job.completed_at = Some(now_ts(&Clock::get()?));
+    job.completed_at = Some(now);
     Ok(())
 }
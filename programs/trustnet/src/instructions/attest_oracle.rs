@@ -0,0 +1,220 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::instructions::oracle_verify::{apply_rejection, release_to_provider};
+use crate::instructions::raise_dispute::{dispute_key, require_no_dispute_accounts};
+use crate::state::dispute::Dispute;
+use crate::state::job_escrow::{JobEscrow, JobStatus, OracleRejectAction, VerifyType};
+use crate::state::oracle::{OracleAttestation, OracleCommittee};
+use crate::state::reputation::{settle_reputations, CategoryReputation};
+use crate::utils::constants::now_ts;
+use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::{Escrow, TokenVault};
+
+#[derive(Accounts)]
+pub struct AttestOracle<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+    #[account(mut)]
+    pub job: Account<'info, JobEscrow>,
+    #[account(
+        mut,
+        seeds = [b"oracle_committee", job.key().as_ref()],
+        bump
+    )]
+    pub committee: Account<'info, OracleCommittee>,
+    #[account(
+        init,
+        payer = oracle,
+        space = OracleAttestation::LEN,
+        seeds = [b"attestation", job.key().as_ref(), oracle.key().as_ref(), committee.round.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, OracleAttestation>,
+    #[account(
+        mut,
+        seeds = [b"job_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+    /// CHECK: provider receives payout
+    #[account(mut, address = job.provider @ TrustNetError::Unauthorized)]
+    pub provider: UncheckedAccount<'info>,
+    /// CHECK: client receives the refund when a rejection fails the job
+    #[account(mut, address = job.client @ TrustNetError::Unauthorized)]
+    pub client: UncheckedAccount<'info>,
+    /// CHECK: the provider's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.provider.as_ref()],
+        bump
+    )]
+    pub provider_reputation: UncheckedAccount<'info>,
+    /// CHECK: the client's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.client.as_ref()],
+        bump
+    )]
+    pub client_reputation: UncheckedAccount<'info>,
+    /// Required when the job has a category.
+    #[account(
+        mut,
+        seeds = [b"category_rep", job.provider.as_ref(), &[job.category.unwrap_or_default()]],
+        bump
+    )]
+    pub provider_category_reputation: Option<Account<'info, CategoryReputation>>,
+    /// Only accepted on the attestation that settles a rejection into a dispute.
+    #[account(
+        init,
+        payer = oracle,
+        space = Dispute::LEN,
+        seeds = [b"dispute", job.key().as_ref()],
+        bump
+    )]
+    pub dispute: Option<Account<'info, Dispute>>,
+    #[account(
+        init,
+        payer = oracle,
+        space = 0,
+        seeds = [b"dispute_vault", dispute_key(&dispute).as_ref()],
+        bump
+    )]
+    pub dispute_vault: Option<SystemAccount<'info>>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"job_token_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = oracle,
+        seeds = [b"dispute_token_vault", dispute_key(&dispute).as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = dispute_vault,
+        token::token_program = token_program
+    )]
+    pub dispute_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub provider_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub client_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AttestOracle>, approved: bool, notes_hash: [u8; 32]) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Submitted, TrustNetError::InvalidStatus);
+    require!(job.verification_type == VerifyType::OracleThreshold, TrustNetError::InvalidStatus);
+
+    let committee = &mut ctx.accounts.committee;
+    let oracle = ctx.accounts.oracle.key();
+    require!(committee.oracles.contains(&oracle), TrustNetError::Unauthorized);
+
+    let attestation = &mut ctx.accounts.attestation;
+    attestation.job = job.key();
+    attestation.oracle = oracle;
+    attestation.round = committee.round;
+    attestation.approved = approved;
+    attestation.notes_hash = notes_hash;
+    attestation.timestamp = now_ts(&Clock::get()?);
+    attestation.bump = *ctx.bumps.get("attestation").unwrap();
+
+    if approved {
+        committee.approvals = committee.approvals.saturating_add(1);
+    } else {
+        committee.rejections = committee.rejections.saturating_add(1);
+    }
+    let escalates = !committee.approved()
+        && committee.rejected()
+        && job.oracle_reject_action() == OracleRejectAction::Dispute;
+    if !escalates {
+        require_no_dispute_accounts(
+            &ctx.accounts.dispute,
+            &ctx.accounts.dispute_vault,
+            &ctx.accounts.dispute_token_vault,
+        )?;
+    }
+    if !committee.approved() && !committee.rejected() {
+        return Ok(());
+    }
+
+    // This attestation settles the round.
+    job.oracle_notes_hash = Some(notes_hash);
+    let token = TokenVault::for_mint(
+        job.mint,
+        &ctx.accounts.job_token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+    let escrow = Escrow::new(b"job_vault", job.key(), &ctx.accounts.job_vault, &ctx.accounts.system_program, token);
+
+    if committee.approved() {
+        release_to_provider(
+            job,
+            &escrow,
+            &ctx.accounts.provider,
+            &ctx.accounts.provider_token_account,
+            &ctx.accounts.treasury,
+            &ctx.accounts.treasury_token_account,
+        )?;
+        settle_reputations(
+            job,
+            &ctx.accounts.provider_reputation,
+            &ctx.accounts.client_reputation,
+            ctx.accounts.provider_category_reputation.as_deref_mut(),
+            true,
+            job.amount,
+            now_ts(&Clock::get()?),
+        )?;
+        return Ok(());
+    }
+
+    apply_rejection(
+        job,
+        &escrow,
+        &ctx.accounts.client,
+        &ctx.accounts.client_token_account,
+        ctx.accounts.dispute.as_deref_mut(),
+        ctx.accounts.dispute_vault.as_deref(),
+        &ctx.accounts.dispute_token_vault,
+        notes_hash,
+    )?;
+    match job.status {
+        JobStatus::Active => {
+            // Rework: the next submission starts a fresh round of attestations.
+            // A saturated round would reuse attestation PDAs, so fail instead.
+            committee.round = committee.round.checked_add(1).ok_or(TrustNetError::InvalidOracleCommittee)?;
+            committee.approvals = 0;
+            committee.rejections = 0;
+        }
+        JobStatus::Disputed => {
+            if let Some(dispute) = ctx.accounts.dispute.as_mut() {
+                dispute.bump = *ctx.bumps.get("dispute").unwrap();
+            }
+        }
+        JobStatus::Failed => settle_reputations(
+            job,
+            &ctx.accounts.provider_reputation,
+            &ctx.accounts.client_reputation,
+            ctx.accounts.provider_category_reputation.as_deref_mut(),
+            false,
+            0,
+            now_ts(&Clock::get()?),
+        )?,
+        _ => {}
+    }
+    Ok(())
+}
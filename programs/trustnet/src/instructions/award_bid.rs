@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::bid::Bid;
+use crate::state::job_escrow::{JobEscrow, JobStatus};
+use crate::utils::constants::{GRACE_PERIOD_SECONDS, now_ts};
+use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::{Escrow, TokenVault};
+
+#[derive(Accounts)]
+pub struct AwardBid<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut, has_one = client @ TrustNetError::Unauthorized)]
+    pub job: Account<'info, JobEscrow>,
+    #[account(
+        seeds = [b"bid", job.key().as_ref(), bid.bidder.as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+    /// CHECK: receives the unspent part of the escrow
+    #[account(mut)]
+    pub client: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"job_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_vault: SystemAccount<'info>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"job_token_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub client_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Client picks any revealed bid once the reveal window has closed.
+pub fn select_handler(ctx: Context<AwardBid>) -> Result<()> {
+    require_keys_eq!(ctx.accounts.authority.key(), ctx.accounts.job.client, TrustNetError::Unauthorized);
+    let auction = ctx.accounts.job.auction.as_ref().ok_or(TrustNetError::BiddingClosed)?;
+    require!(now_ts(&Clock::get()?) > auction.reveal_deadline, TrustNetError::DeadlineNotReached);
+    award(ctx)
+}
+
+/// Anyone can award the lowest bid if the client has not selected one within the grace period.
+pub fn finalize_handler(ctx: Context<AwardBid>) -> Result<()> {
+    let auction = ctx.accounts.job.auction.as_ref().ok_or(TrustNetError::BiddingClosed)?;
+    require!(
+        now_ts(&Clock::get()?) > auction.reveal_deadline + GRACE_PERIOD_SECONDS,
+        TrustNetError::DeadlineNotReached
+    );
+    require_keys_eq!(ctx.accounts.bid.bidder, auction.lowest_bidder, TrustNetError::NoValidBid);
+    award(ctx)
+}
+
+fn award(ctx: Context<AwardBid>) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Created, TrustNetError::InvalidStatus);
+    let bid = &ctx.accounts.bid;
+    let amount = bid.amount.filter(|_| bid.revealed).ok_or(TrustNetError::NoValidBid)?;
+
+    let auction = job.auction.as_mut().ok_or(TrustNetError::BiddingClosed)?;
+    require!(!auction.awarded, TrustNetError::InvalidStatus);
+    auction.awarded = true;
+
+    let refund = job.amount.saturating_sub(amount);
+    job.amount = amount;
+    job.provider = bid.bidder;
+
+    let token = TokenVault::for_mint(
+        job.mint,
+        &ctx.accounts.job_token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+    let escrow = Escrow::new(b"job_vault", job.key(), &ctx.accounts.job_vault, &ctx.accounts.system_program, token);
+    escrow.pay(&ctx.accounts.client, &ctx.accounts.client_token_account, refund)?;
+    Ok(())
+}
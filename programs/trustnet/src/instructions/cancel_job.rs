@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::job_escrow::{JobEscrow, JobStatus, MilestoneStatus};
+use crate::state::reputation::AgentReputation;
+use crate::utils::constants::now_ts;
+use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::{Escrow, TokenVault};
+
+#[derive(Accounts)]
+pub struct CancelJob<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+    /// CHECK: must be `job.provider` and sign to cancel an active job; receives its stake back.
+    /// Unused for a created job, so an open job can pass any account.
+    #[account(mut)]
+    pub provider: UncheckedAccount<'info>,
+    #[account(mut, has_one = client @ TrustNetError::Unauthorized)]
+    pub job: Account<'info, JobEscrow>,
+    /// Required to cancel an active job the provider accepted with its profile.
+    #[account(
+        mut,
+        seeds = [b"reputation", job.provider.as_ref()],
+        bump
+    )]
+    pub provider_reputation: Option<Account<'info, AgentReputation>>,
+    #[account(
+        mut,
+        seeds = [b"job_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_vault: SystemAccount<'info>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"job_token_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub client_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub provider_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CancelJob>) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+
+    let token = TokenVault::for_mint(
+        job.mint,
+        &ctx.accounts.job_token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+    let escrow = Escrow::new(b"job_vault", job.key(), &ctx.accounts.job_vault, &ctx.accounts.system_program, token);
+
+    match job.status {
+        JobStatus::Created => {
+            escrow.pay(&ctx.accounts.client, &ctx.accounts.client_token_account, job.amount)?;
+        }
+        JobStatus::Active => {
+            require_keys_eq!(ctx.accounts.provider.key(), job.provider, TrustNetError::Unauthorized);
+            require!(ctx.accounts.provider.is_signer, TrustNetError::ProviderConsentRequired);
+            require!(
+                job.milestones.iter().all(|m| m.status != MilestoneStatus::Disputed),
+                TrustNetError::InvalidStatus
+            );
+            if job.provider_has_reputation {
+                let reputation = ctx.accounts.provider_reputation.as_mut().ok_or(TrustNetError::MissingProviderReputation)?;
+                reputation.open_jobs = reputation.open_jobs.saturating_sub(1);
+            }
+            escrow.pay(&ctx.accounts.provider, &ctx.accounts.provider_token_account, job.provider_stake)?;
+            // Milestones already released stay with the provider.
+            escrow.pay(&ctx.accounts.client, &ctx.accounts.client_token_account, job.outstanding_amount())?;
+        }
+        _ => return Err(TrustNetError::InvalidStatus.into()),
+    }
+
+    job.status = JobStatus::Cancelled;
+    job.completed_at = Some(now_ts(&Clock::get()?));
+    Ok(())
+}
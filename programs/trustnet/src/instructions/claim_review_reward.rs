@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::state::job_escrow::{JobEscrow, JobStatus};
+use crate::state::review::{Review, ReviewPanel};
+use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::transfer_from_vault;
+
+#[derive(Accounts)]
+pub struct ClaimReviewReward<'info> {
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+    #[account(address = panel.job @ TrustNetError::InvalidReviewPanel)]
+    pub job: Account<'info, JobEscrow>,
+    #[account(
+        seeds = [b"review_panel", panel.job.as_ref()],
+        bump
+    )]
+    pub panel: Account<'info, ReviewPanel>,
+    #[account(
+        mut,
+        has_one = reviewer @ TrustNetError::Unauthorized,
+        seeds = [b"review", panel.job.as_ref(), reviewer.key().as_ref()],
+        bump
+    )]
+    pub review: Account<'info, Review>,
+    #[account(
+        mut,
+        seeds = [b"review_vault", panel.job.as_ref()],
+        bump
+    )]
+    pub review_vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Reviewers who sided with the majority get their bond back plus a share of the
+/// protocol fee and the minority's forfeited bonds. If the job left `Submitted`
+/// (e.g. through a dispute) before the panel reached a majority, every reviewer
+/// gets their bond back.
+pub fn handler(ctx: Context<ClaimReviewReward>) -> Result<()> {
+    let panel = &ctx.accounts.panel;
+    let review = &mut ctx.accounts.review;
+    require!(!review.claimed, TrustNetError::InvalidStatus);
+    let amount = match panel.outcome {
+        Some(outcome) => {
+            require!(review.approved == outcome, TrustNetError::ReviewerNotEligible);
+            panel.bond.saturating_add(panel.reward_share())
+        }
+        None => {
+            require!(ctx.accounts.job.status != JobStatus::Submitted, TrustNetError::InvalidStatus);
+            panel.bond
+        }
+    };
+    review.claimed = true;
+
+    let seeds: &[&[u8]] = &[b"review_vault", panel.job.as_ref()];
+    transfer_from_vault(
+        &ctx.accounts.review_vault.to_account_info(),
+        &ctx.accounts.reviewer.to_account_info(),
+        &ctx.accounts.system_program,
+        seeds,
+        amount,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::state::bid::Bid;
+use crate::state::job_escrow::{JobEscrow, JobStatus};
+use crate::state::reputation::AgentReputation;
+use crate::utils::constants::now_ts;
+use crate::utils::errors::TrustNetError;
+
+#[derive(Accounts)]
+pub struct CommitBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(mut)]
+    pub job: Account<'info, JobEscrow>,
+    /// Required when the job sets provider requirements.
+    #[account(
+        seeds = [b"reputation", bidder.key().as_ref()],
+        bump
+    )]
+    pub bidder_reputation: Option<Account<'info, AgentReputation>>,
+    #[account(
+        init,
+        payer = bidder,
+        space = Bid::LEN,
+        seeds = [b"bid", job.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CommitBid>, bid_hash: [u8; 32]) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Created, TrustNetError::InvalidStatus);
+    require_keys_neq!(ctx.accounts.bidder.key(), job.client, TrustNetError::Unauthorized);
+
+    let now = now_ts(&Clock::get()?);
+    job.check_provider_eligible(ctx.accounts.bidder_reputation.as_deref(), now)?;
+
+    let auction = job.auction.as_mut().ok_or(TrustNetError::BiddingClosed)?;
+    require!(now <= auction.commit_deadline, TrustNetError::BiddingClosed);
+    auction.bid_count = auction.bid_count.saturating_add(1);
+
+    let bid = &mut ctx.accounts.bid;
+    bid.job = job.key();
+    bid.bidder = ctx.accounts.bidder.key();
+    bid.commit_hash = bid_hash;
+    bid.revealed = false;
+    bid.amount = None;
+    bid.committed_at = now;
+    bid.bump = *ctx.bumps.get("bid").unwrap();
+    Ok(())
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::state::arbiter::Arbiter;
 use crate::state::dispute::{Dispute, DisputeStatus, VoteCommitment};
+use crate::utils::constants::now_ts;
 use crate::utils::errors::TrustNetError;
 
 #[derive(Accounts)]
@@ -25,6 +26,12 @@ pub struct CommitVote<'info> {
 pub fn handler(ctx: Context<CommitVote>, vote_hash: [u8; 32]) -> Result<()> {
     let dispute = &mut ctx.accounts.dispute;
     require!(dispute.status == DisputeStatus::CommitPhase, TrustNetError::InvalidDisputeState);
+    require!(now_ts(&Clock::get()?) <= dispute.commit_deadline, TrustNetError::CommitPhaseClosed);
+    require!(ctx.accounts.arbiter.active, TrustNetError::ArbiterInactive);
+    require!(
+        dispute.selected_arbiters.contains(&ctx.accounts.arbiter.key()),
+        TrustNetError::ArbiterNotSelected
+    );
 
     let vote = &mut ctx.accounts.vote_commitment;
     vote.dispute = dispute.key();
@@ -32,10 +39,12 @@ pub fn handler(ctx: Context<CommitVote>, vote_hash: [u8; 32]) -> Result<()> {
     vote.commit_hash = vote_hash;
     vote.revealed = false;
     vote.vote = None;
+    vote.weight = 0;
     vote.bump = *ctx.bumps.get("vote_commitment").unwrap();
+    dispute.commit_count = dispute.commit_count.saturating_add(1);
 
-    if !dispute.selected_arbiters.contains(&ctx.accounts.arbiter.key()) {
-        dispute.selected_arbiters.push(ctx.accounts.arbiter.key());
-    }
+    // Held until resolve_dispute releases it; blocks stake withdrawal meanwhile.
+    let arbiter = &mut ctx.accounts.arbiter;
+    arbiter.open_cases = arbiter.open_cases.saturating_add(1);
     Ok(())
 }
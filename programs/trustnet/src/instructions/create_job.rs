@@ -1,16 +1,18 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::job_escrow::{JobEscrow, JobStatus, VerifyType};
+use crate::state::job_escrow::{JobEscrow, JobStatus, ProviderRequirements, VerifyType};
 use crate::utils::constants::{now_ts, BPS_DENOMINATOR, PROTOCOL_FEE_BPS};
 use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::deposit_tokens;
 
 #[derive(Accounts)]
 #[instruction(job_id: [u8;32])]
 pub struct CreateJob<'info> {
     #[account(mut)]
     pub client: Signer<'info>,
-    /// CHECK: provider can be empty at creation
+    /// CHECK: provider can be empty (default pubkey) at creation for an open job
     pub provider: UncheckedAccount<'info>,
     #[account(
         init,
@@ -36,6 +38,20 @@ pub struct CreateJob<'info> {
         bump
     )]
     pub treasury: SystemAccount<'info>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub client_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = client,
+        seeds = [b"job_token_vault", job.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = job_vault,
+        token::token_program = token_program
+    )]
+    pub job_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
 
@@ -63,16 +79,35 @@ pub fn handler(
     job.submitted_at = None;
     job.completed_at = None;
     job.terms_hash = terms_hash;
+    job.mint = None;
+    job.requirements = ProviderRequirements::default();
+    job.auction = None;
+    job.revision_count = 0;
+    job.feedback_hash = None;
+    job.oracle_notes_hash = None;
+    job.submission_count = 0;
+    job.submission_hash = None;
+    job.category = None;
+    job.provider_has_reputation = false;
     job.bump = *ctx.bumps.get("job").unwrap();
 
-    let cpi = CpiContext::new(
-        ctx.accounts.system_program.to_account_info(),
-        system_program::Transfer {
-            from: ctx.accounts.client.to_account_info(),
-            to: ctx.accounts.job_vault.to_account_info(),
-        },
-    );
-    system_program::transfer(cpi, amount)?;
+    if let Some(job_token_vault) = ctx.accounts.job_token_vault.as_mut() {
+        let mint = ctx.accounts.mint.as_ref().ok_or(TrustNetError::MissingTokenAccount)?;
+        let from = ctx.accounts.client_token_account.as_ref().ok_or(TrustNetError::MissingTokenAccount)?;
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(TrustNetError::MissingTokenAccount)?;
+        // Record what the vault received so Token-2022 transfer fees never leave it short.
+        job.amount = deposit_tokens(token_program, mint, from, job_token_vault, &ctx.accounts.client, amount)?;
+        job.mint = Some(mint.key());
+    } else {
+        let cpi = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.client.to_account_info(),
+                to: ctx.accounts.job_vault.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi, amount)?;
+    }
 
     let _fee_preview = amount
         .checked_mul(PROTOCOL_FEE_BPS)
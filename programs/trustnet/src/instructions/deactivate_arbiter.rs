@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::state::arbiter::{Arbiter, ArbiterRegistry, ArbiterRegistryPage};
+use crate::utils::constants::{ARBITER_WITHDRAW_COOLDOWN_SECONDS, now_ts};
+use crate::utils::errors::TrustNetError;
+
+#[derive(Accounts)]
+pub struct DeactivateArbiter<'info> {
+    pub authority: Signer<'info>,
+    #[account(mut, has_one = authority @ TrustNetError::Unauthorized)]
+    pub arbiter: Account<'info, Arbiter>,
+    #[account(
+        mut,
+        seeds = [b"arbiter_registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, ArbiterRegistry>,
+    #[account(
+        mut,
+        seeds = [b"arbiter_registry_page", arbiter.registry_page.to_le_bytes().as_ref()],
+        bump = registry_page.bump
+    )]
+    pub registry_page: Account<'info, ArbiterRegistryPage>,
+}
+
+/// Takes the arbiter out of the registry so it is no longer drawn, and starts the
+/// withdrawal cooldown. Disputes it already voted on still count against it.
+pub fn handler(ctx: Context<DeactivateArbiter>) -> Result<()> {
+    let arbiter = &mut ctx.accounts.arbiter;
+    require!(arbiter.active, TrustNetError::ArbiterInactive);
+    ctx.accounts.registry_page.remove(&mut ctx.accounts.registry, &arbiter.key())?;
+    arbiter.active = false;
+    arbiter.withdrawable_at = now_ts(&Clock::get()?).saturating_add(ARBITER_WITHDRAW_COOLDOWN_SECONDS);
+    Ok(())
+}
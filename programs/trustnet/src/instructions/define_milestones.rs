@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::state::job_escrow::{JobEscrow, JobStatus, Milestone, MilestoneSpec, MilestoneStatus, VerifyType};
+use crate::utils::constants::{MAX_MILESTONES, MIN_MILESTONES};
+use crate::utils::errors::TrustNetError;
+
+#[derive(Accounts)]
+pub struct DefineMilestones<'info> {
+    pub client: Signer<'info>,
+    #[account(mut, has_one = client @ TrustNetError::Unauthorized)]
+    pub job: Account<'info, JobEscrow>,
+}
+
+pub fn handler(ctx: Context<DefineMilestones>, milestones: Vec<MilestoneSpec>) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Created, TrustNetError::InvalidStatus);
+    require!(job.milestones.is_empty(), TrustNetError::InvalidMilestone);
+    // Oracle, review and proof settlement pay the whole job at once; only these release per milestone.
+    require!(
+        job.verification_type == VerifyType::ClientApproval || job.verification_type == VerifyType::DeadlineAuto,
+        TrustNetError::InvalidMilestone
+    );
+    // An awarded bid changes the job amount, so milestones and auctions don't mix.
+    require!(job.auction.is_none(), TrustNetError::InvalidMilestone);
+    require!(
+        milestones.len() >= MIN_MILESTONES && milestones.len() <= MAX_MILESTONES,
+        TrustNetError::InvalidMilestone
+    );
+
+    let mut total: u64 = 0;
+    let mut previous_deadline = i64::MIN;
+    for spec in milestones.iter() {
+        require!(spec.amount > 0, TrustNetError::InvalidMilestone);
+        require!(
+            spec.deadline >= previous_deadline && spec.deadline <= job.deadline,
+            TrustNetError::InvalidMilestone
+        );
+        total = total.checked_add(spec.amount).ok_or(TrustNetError::InvalidMilestone)?;
+        previous_deadline = spec.deadline;
+    }
+    require!(total == job.amount, TrustNetError::InvalidMilestone);
+
+    job.milestones = milestones
+        .into_iter()
+        .map(|spec| Milestone {
+            amount: spec.amount,
+            deadline: spec.deadline,
+            terms_hash: spec.terms_hash,
+            status: MilestoneStatus::Pending,
+            submitted_at: None,
+            submission_index: None,
+            submission_hash: None,
+        })
+        .collect();
+    Ok(())
+}
@@ -1,9 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::job_escrow::{JobEscrow, JobStatus, VerifyType};
-use crate::utils::constants::{GRACE_PERIOD_SECONDS, now_ts, BPS_DENOMINATOR, PROTOCOL_FEE_BPS};
+use crate::instructions::approve_completion::finish_milestone_job;
+use crate::instructions::resolve_dispute::slash_provider_stake;
+use crate::state::job_escrow::{JobEscrow, JobStatus, MilestoneStatus, VerifyType};
+use crate::state::reputation::{load_reputation, record_settlement, store_reputation, CategoryReputation};
+use crate::utils::constants::{
+    GRACE_PERIOD_SECONDS, now_ts, BPS_DENOMINATOR, PROTOCOL_FEE_BPS, PROVIDER_SLASH_BPS, REVISION_WINDOW_SECONDS,
+};
 use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::{Escrow, TokenVault};
 
 #[derive(Accounts)]
 pub struct ExpireJob<'info> {
@@ -22,30 +28,170 @@ pub struct ExpireJob<'info> {
     )]
     pub treasury: SystemAccount<'info>,
     /// CHECK: client refund
-    #[account(mut)]
+    #[account(mut, address = job.client @ TrustNetError::Unauthorized)]
     pub client: UncheckedAccount<'info>,
     /// CHECK: provider payout
-    #[account(mut)]
+    #[account(mut, address = job.provider @ TrustNetError::Unauthorized)]
     pub provider: UncheckedAccount<'info>,
+    /// CHECK: the provider's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.provider.as_ref()],
+        bump
+    )]
+    pub provider_reputation: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"rep_vault", job.provider.as_ref()],
+        bump
+    )]
+    pub provider_rep_vault: SystemAccount<'info>,
+    /// CHECK: the client's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.client.as_ref()],
+        bump
+    )]
+    pub client_reputation: UncheckedAccount<'info>,
+    /// Required when the job has a category.
+    #[account(
+        mut,
+        seeds = [b"category_rep", job.provider.as_ref(), &[job.category.unwrap_or_default()]],
+        bump
+    )]
+    pub provider_category_reputation: Option<Account<'info, CategoryReputation>>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"job_token_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub client_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub provider_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<ExpireJob>) -> Result<()> {
+/// Returns the lamports slashed from the provider's reputation stake when an `Active` job
+/// expires or milestones lapse undelivered.
+pub fn handler(ctx: Context<ExpireJob>) -> Result<u64> {
     let job = &mut ctx.accounts.job;
     let now = now_ts(&Clock::get()?);
+    let mut provider_reputation = load_reputation(&ctx.accounts.provider_reputation)?;
+    let mut client_reputation = load_reputation(&ctx.accounts.client_reputation)?;
+
+    let token = TokenVault::for_mint(
+        job.mint,
+        &ctx.accounts.job_token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+    let escrow = Escrow::new(b"job_vault", job.key(), &ctx.accounts.job_vault, &ctx.accounts.system_program, token);
+
+    if !job.milestones.is_empty() {
+        require!(job.status == JobStatus::Active, TrustNetError::InvalidStatus);
+        let auto_release = job.verification_type == VerifyType::DeadlineAuto;
+        let mut refund: u64 = 0;
+        let mut release: u64 = 0;
+        for milestone in job.milestones.iter_mut() {
+            match milestone.status {
+                MilestoneStatus::Pending if now > milestone.deadline => {
+                    refund = refund.saturating_add(milestone.amount);
+                    milestone.status = MilestoneStatus::Refunded;
+                }
+                MilestoneStatus::Submitted if auto_release && now > milestone.deadline + GRACE_PERIOD_SECONDS => {
+                    release = release.saturating_add(milestone.amount);
+                    milestone.status = MilestoneStatus::Released;
+                }
+                _ => {}
+            }
+        }
+        require!(refund > 0 || release > 0, TrustNetError::DeadlineNotReached);
+        if refund > 0 && job.provider_has_reputation {
+            require!(provider_reputation.is_some(), TrustNetError::MissingProviderReputation);
+        }
+        // Missed milestones cost the share of the whole-job slash that they are of the job.
+        let slash_bps = (PROVIDER_SLASH_BPS as u128 * refund as u128 / job.amount.max(1) as u128) as u64;
+
+        let fee = release
+            .checked_mul(PROTOCOL_FEE_BPS)
+            .and_then(|v| v.checked_div(BPS_DENOMINATOR))
+            .unwrap_or(0);
+        escrow.pay(&ctx.accounts.client, &ctx.accounts.client_token_account, refund)?;
+        escrow.pay(&ctx.accounts.provider, &ctx.accounts.provider_token_account, release.saturating_sub(fee))?;
+        escrow.pay(&ctx.accounts.treasury, &ctx.accounts.treasury_token_account, fee)?;
+        finish_milestone_job(
+            job,
+            &escrow,
+            &ctx.accounts.client,
+            &ctx.accounts.client_token_account,
+            &ctx.accounts.provider,
+            &ctx.accounts.provider_token_account,
+            now,
+        )?;
+        if job.status == JobStatus::Completed {
+            record_settlement(
+                job,
+                provider_reputation.as_mut(),
+                client_reputation.as_mut(),
+                ctx.accounts.provider_category_reputation.as_deref_mut(),
+                job.fully_delivered(),
+                job.released_amount(),
+                now,
+            )?;
+        }
+        let slashed = slash_provider_stake(
+            provider_reputation.as_mut(),
+            slash_bps,
+            &ctx.accounts.provider_rep_vault,
+            &ctx.accounts.client,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?;
+        store_reputation(&ctx.accounts.provider_reputation, &provider_reputation)?;
+        store_reputation(&ctx.accounts.client_reputation, &client_reputation)?;
+        return Ok(slashed);
+    }
 
     match job.status {
         JobStatus::Active => {
             require!(now > job.deadline, TrustNetError::DeadlineNotReached);
+            // Work that arrived before the deadline was rejected, not missed: the provider keeps
+            // a full revision window after its last submission.
+            if let Some(submitted_at) = job.submitted_at {
+                require!(
+                    submitted_at > job.deadline || now > submitted_at.saturating_add(REVISION_WINDOW_SECONDS),
+                    TrustNetError::DeadlineNotReached
+                );
+            }
             let total = job.amount.checked_add(job.provider_stake).ok_or(TrustNetError::InsufficientFunds)?;
-            transfer_from_vault(
-                &ctx.accounts.job_vault,
+            escrow.pay(&ctx.accounts.client, &ctx.accounts.client_token_account, total)?;
+            job.status = JobStatus::Expired;
+            record_settlement(
+                job,
+                provider_reputation.as_mut(),
+                client_reputation.as_mut(),
+                ctx.accounts.provider_category_reputation.as_deref_mut(),
+                false,
+                0,
+                now,
+            )?;
+            let slashed = slash_provider_stake(
+                provider_reputation.as_mut(),
+                PROVIDER_SLASH_BPS,
+                &ctx.accounts.provider_rep_vault,
                 &ctx.accounts.client,
+                &ctx.accounts.treasury,
                 &ctx.accounts.system_program,
-                &[b"job_vault", job.key().as_ref()],
-                total,
             )?;
-            job.status = JobStatus::Expired;
+            store_reputation(&ctx.accounts.provider_reputation, &provider_reputation)?;
+            store_reputation(&ctx.accounts.client_reputation, &client_reputation)?;
+            return Ok(slashed);
         }
         JobStatus::Submitted => {
             require!(job.verification_type == VerifyType::DeadlineAuto, TrustNetError::InvalidStatus);
@@ -56,47 +202,22 @@ pub fn handler(ctx: Context<ExpireJob>) -> Result<()> {
                 .and_then(|v| v.checked_div(BPS_DENOMINATOR))
                 .unwrap_or(0);
             let payout = total.saturating_sub(fee);
-            transfer_from_vault(
-                &ctx.accounts.job_vault,
-                &ctx.accounts.provider,
-                &ctx.accounts.system_program,
-                &[b"job_vault", job.key().as_ref()],
-                payout,
-            )?;
-            if fee > 0 {
-                transfer_from_vault(
-                    &ctx.accounts.job_vault,
-                    &ctx.accounts.treasury,
-                    &ctx.accounts.system_program,
-                    &[b"job_vault", job.key().as_ref()],
-                    fee,
-                )?;
-            }
+            escrow.pay(&ctx.accounts.provider, &ctx.accounts.provider_token_account, payout)?;
+            escrow.pay(&ctx.accounts.treasury, &ctx.accounts.treasury_token_account, fee)?;
             job.status = JobStatus::Completed;
+            record_settlement(
+                job,
+                provider_reputation.as_mut(),
+                client_reputation.as_mut(),
+                ctx.accounts.provider_category_reputation.as_deref_mut(),
+                true,
+                job.amount,
+                now,
+            )?;
         }
         _ => return Err(TrustNetError::InvalidStatus.into()),
     }
-    Ok(())
-}
-
-fn transfer_from_vault(
-    vault: &SystemAccount,
-    to: &AccountInfo,
-    system_program: &Program<System>,
-    seeds: &[&[u8]],
-    amount: u64,
-) -> Result<()> {
-    let (pda, bump) = Pubkey::find_program_address(seeds, &crate::ID);
-    require!(pda == vault.key(), TrustNetError::Unauthorized);
-    let signer_seeds: &[&[u8]] = &[seeds[0], seeds[1], &[bump]];
-    let transfer = CpiContext::new_with_signer(
-        system_program.to_account_info(),
-        system_program::Transfer {
-            from: vault.to_account_info(),
-            to: to.clone(),
-        },
-        &[signer_seeds],
-    );
-    system_program::transfer(transfer, amount)?;
-    Ok(())
+    store_reputation(&ctx.accounts.provider_reputation, &provider_reputation)?;
+    store_reputation(&ctx.accounts.client_reputation, &client_reputation)?;
+    Ok(0)
 }
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::state::reputation::{AgentReputation, CategoryReputation, ReputationScore};
+use crate::utils::constants::now_ts;
+#[cfg(feature = "cpi")]
+use crate::utils::errors::TrustNetError;
+
+#[derive(Accounts)]
+pub struct GetReputationScore<'info> {
+    #[account(
+        seeds = [b"reputation", reputation.agent.as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, AgentReputation>,
+}
+
+/// Read-only: computes the agent's score at the current clock. Anchor writes the
+/// returned value to the transaction's return data.
+pub fn handler(ctx: Context<GetReputationScore>) -> Result<ReputationScore> {
+    let now = now_ts(&Clock::get()?);
+    Ok(ctx.accounts.reputation.score(now))
+}
+
+#[derive(Accounts)]
+pub struct GetCategoryScore<'info> {
+    pub reputation: Account<'info, AgentReputation>,
+    #[account(
+        seeds = [b"category_rep", reputation.agent.as_ref(), &[category_reputation.category]],
+        bump = category_reputation.bump
+    )]
+    pub category_reputation: Account<'info, CategoryReputation>,
+}
+
+/// Read-only: scores the agent's record in one category, staked by its global stake.
+pub fn category_handler(ctx: Context<GetCategoryScore>) -> Result<ReputationScore> {
+    let now = now_ts(&Clock::get()?);
+    Ok(ctx.accounts.category_reputation.score(ctx.accounts.reputation.stake_amount, now))
+}
+
+/// Reads `agent`'s TrustNet reputation score from another program, e.g. to gate an
+/// action on a minimum score. `reputation` must be the agent's `[b"reputation", agent]`
+/// PDA, so a caller cannot pass in another agent's profile.
+#[cfg(feature = "cpi")]
+pub fn query<'info>(
+    trustnet_program: AccountInfo<'info>,
+    agent: &Pubkey,
+    reputation: AccountInfo<'info>,
+) -> Result<ReputationScore> {
+    require_keys_eq!(trustnet_program.key(), crate::ID, ErrorCode::InvalidProgramId);
+    let (pda, _) = Pubkey::find_program_address(&[b"reputation", agent.as_ref()], &crate::ID);
+    require_keys_eq!(reputation.key(), pda, TrustNetError::ReputationAccountMismatch);
+    let ctx = CpiContext::new(trustnet_program, crate::cpi::accounts::GetReputationScore { reputation });
+    Ok(crate::cpi::get_reputation_score(ctx)?.get())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::state::arbiter::ArbiterRegistry;
+
+#[derive(Accounts)]
+pub struct InitArbiterRegistry<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = ArbiterRegistry::LEN,
+        seeds = [b"arbiter_registry"],
+        bump
+    )]
+    pub registry: Account<'info, ArbiterRegistry>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitArbiterRegistry>) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.page_count = 0;
+    registry.arbiter_count = 0;
+    registry.total_stake = 0;
+    registry.bump = *ctx.bumps.get("registry").unwrap();
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::state::reputation::{AgentReputation, CategoryReputation};
+use crate::utils::constants::{now_ts, RATING_PRIOR_MEAN};
+
+#[derive(Accounts)]
+#[instruction(category: u8)]
+pub struct InitCategoryReputation<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    #[account(has_one = agent)]
+    pub reputation: Account<'info, AgentReputation>,
+    #[account(
+        init,
+        payer = agent,
+        space = CategoryReputation::LEN,
+        seeds = [b"category_rep", agent.key().as_ref(), &[category]],
+        bump
+    )]
+    pub category_reputation: Account<'info, CategoryReputation>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitCategoryReputation>, category: u8) -> Result<()> {
+    let track = &mut ctx.accounts.category_reputation;
+    track.agent = ctx.accounts.agent.key();
+    track.category = category;
+    track.jobs_completed = 0;
+    track.jobs_failed = 0;
+    track.volume = 0;
+    track.avg_rating = 0;
+    track.bayesian_rating = RATING_PRIOR_MEAN as u16;
+    track.rating_count = 0;
+    track.rating_sum = 0;
+    track.created_at = now_ts(&Clock::get()?);
+    track.last_active = track.created_at;
+    track.bump = *ctx.bumps.get("category_reputation").unwrap();
+    Ok(())
+}
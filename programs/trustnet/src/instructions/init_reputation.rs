@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
 use crate::state::reputation::AgentReputation;
-use crate::utils::constants::{MIN_REPUTATION_STAKE_LAMPORTS, now_ts, MAX_SPECIALIZATIONS_LEN};
+use crate::utils::constants::{MIN_REPUTATION_STAKE_LAMPORTS, now_ts, MAX_SPECIALIZATIONS_LEN, RATING_PRIOR_MEAN};
 use crate::utils::errors::TrustNetError;
 
 #[derive(Accounts)]
@@ -40,11 +40,20 @@ pub fn handler(ctx: Context<InitReputation>, stake_lamports: u64, specialization
     reputation.total_disputes_lost = 0;
     reputation.total_volume = 0;
     reputation.avg_rating = 0;
+    reputation.bayesian_rating = RATING_PRIOR_MEAN as u16;
     reputation.rating_count = 0;
+    reputation.rating_sum = 0;
+    reputation.weighted_rating_sum = 0;
+    reputation.rating_weight_total = 0;
+    reputation.weighted_avg_rating = 0;
     reputation.specializations = specializations;
     reputation.created_at = now_ts(&Clock::get()?);
     reputation.last_active = reputation.created_at;
     reputation.stake_amount = stake_lamports;
+    reputation.unbonding_amount = 0;
+    reputation.unbonding_ready_at = 0;
+    reputation.closed = false;
+    reputation.open_jobs = 0;
     reputation.bump = *ctx.bumps.get("reputation").unwrap();
 
     let cpi = CpiContext::new(
@@ -1,14 +1,39 @@
 pub mod create_job;
 pub mod accept_job;
+pub mod define_milestones;
+pub mod set_job_requirements;
+pub mod set_job_category;
+pub mod open_bidding;
+pub mod commit_bid;
+pub mod reveal_bid;
+pub mod award_bid;
 pub mod submit_completion;
 pub mod approve_completion;
+pub mod reject_submission;
 pub mod oracle_verify;
+pub mod set_oracle_committee;
+pub mod attest_oracle;
+pub mod set_review_panel;
+pub mod submit_review;
+pub mod claim_review_reward;
+pub mod register_verifying_key;
+pub mod submit_zk_proof;
 pub mod raise_dispute;
+pub mod select_arbiters;
 pub mod commit_vote;
 pub mod reveal_vote;
 pub mod resolve_dispute;
 pub mod expire_job;
+pub mod cancel_job;
 pub mod rate_job;
 pub mod stake_reputation;
+pub mod unstake_reputation;
+pub mod get_reputation_score;
 pub mod register_arbiter;
+pub mod add_arbiter_stake;
+pub mod deactivate_arbiter;
+pub mod withdraw_arbiter_stake;
+pub mod init_arbiter_registry;
+pub mod add_arbiter_registry_page;
 pub mod init_reputation;
+pub mod init_category_reputation;
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::state::job_escrow::{Auction, JobEscrow, JobStatus};
+use crate::utils::constants::now_ts;
+use crate::utils::errors::TrustNetError;
+
+#[derive(Accounts)]
+pub struct OpenBidding<'info> {
+    pub client: Signer<'info>,
+    #[account(mut, has_one = client @ TrustNetError::Unauthorized)]
+    pub job: Account<'info, JobEscrow>,
+}
+
+pub fn handler(ctx: Context<OpenBidding>, commit_deadline: i64, reveal_deadline: i64) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Created, TrustNetError::InvalidStatus);
+    require!(job.is_open() && job.auction.is_none(), TrustNetError::InvalidStatus);
+    require!(job.milestones.is_empty(), TrustNetError::InvalidMilestone);
+
+    let now = now_ts(&Clock::get()?);
+    require!(
+        now < commit_deadline && commit_deadline < reveal_deadline && reveal_deadline < job.deadline,
+        TrustNetError::BiddingClosed
+    );
+
+    job.auction = Some(Auction {
+        commit_deadline,
+        reveal_deadline,
+        bid_count: 0,
+        lowest_bidder: Pubkey::default(),
+        lowest_amount: 0,
+        awarded: false,
+    });
+    Ok(())
+}
@@ -1,12 +1,17 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::job_escrow::{JobEscrow, JobStatus, VerifyType};
+use crate::instructions::raise_dispute::{dispute_key, escalate_to_dispute, require_no_dispute_accounts};
+use crate::state::dispute::Dispute;
+use crate::state::job_escrow::{JobEscrow, JobStatus, OracleRejectAction, VerifyType};
+use crate::state::reputation::{settle_reputations, CategoryReputation};
 use crate::utils::constants::{BPS_DENOMINATOR, PROTOCOL_FEE_BPS, now_ts};
 use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::{Escrow, TokenVault};
 
 #[derive(Accounts)]
 pub struct OracleVerify<'info> {
+    #[account(mut)]
     pub oracle: Signer<'info>,
     #[account(mut)]
     pub job: Account<'info, JobEscrow>,
@@ -23,53 +28,199 @@ pub struct OracleVerify<'info> {
     )]
     pub treasury: SystemAccount<'info>,
     /// CHECK: provider receives payout
-    #[account(mut)]
+    #[account(mut, address = job.provider @ TrustNetError::Unauthorized)]
     pub provider: UncheckedAccount<'info>,
+    /// CHECK: client receives the refund when a rejection fails the job
+    #[account(mut, address = job.client @ TrustNetError::Unauthorized)]
+    pub client: UncheckedAccount<'info>,
+    /// CHECK: the provider's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.provider.as_ref()],
+        bump
+    )]
+    pub provider_reputation: UncheckedAccount<'info>,
+    /// CHECK: the client's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.client.as_ref()],
+        bump
+    )]
+    pub client_reputation: UncheckedAccount<'info>,
+    /// Required when the job has a category.
+    #[account(
+        mut,
+        seeds = [b"category_rep", job.provider.as_ref(), &[job.category.unwrap_or_default()]],
+        bump
+    )]
+    pub provider_category_reputation: Option<Account<'info, CategoryReputation>>,
+    /// Only accepted when a rejection opens a dispute.
+    #[account(
+        init,
+        payer = oracle,
+        space = Dispute::LEN,
+        seeds = [b"dispute", job.key().as_ref()],
+        bump
+    )]
+    pub dispute: Option<Account<'info, Dispute>>,
+    #[account(
+        init,
+        payer = oracle,
+        space = 0,
+        seeds = [b"dispute_vault", dispute_key(&dispute).as_ref()],
+        bump
+    )]
+    pub dispute_vault: Option<SystemAccount<'info>>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"job_token_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = oracle,
+        seeds = [b"dispute_token_vault", dispute_key(&dispute).as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = dispute_vault,
+        token::token_program = token_program
+    )]
+    pub dispute_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub provider_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub client_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<OracleVerify>, approved: bool, _notes_hash: [u8; 32]) -> Result<()> {
+pub fn handler(ctx: Context<OracleVerify>, approved: bool, notes_hash: [u8; 32]) -> Result<()> {
     let job = &mut ctx.accounts.job;
     require!(job.status == JobStatus::Submitted, TrustNetError::InvalidStatus);
     require!(job.verification_type == VerifyType::OracleVerify, TrustNetError::InvalidStatus);
     let oracle_key = Pubkey::new_from_array(job.verification_data[..32].try_into().unwrap());
     require!(oracle_key == ctx.accounts.oracle.key(), TrustNetError::Unauthorized);
+    job.oracle_notes_hash = Some(notes_hash);
+    if approved || job.oracle_reject_action() != OracleRejectAction::Dispute {
+        require_no_dispute_accounts(
+            &ctx.accounts.dispute,
+            &ctx.accounts.dispute_vault,
+            &ctx.accounts.dispute_token_vault,
+        )?;
+    }
+
+    let token = TokenVault::for_mint(
+        job.mint,
+        &ctx.accounts.job_token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+    let escrow = Escrow::new(b"job_vault", job.key(), &ctx.accounts.job_vault, &ctx.accounts.system_program, token);
 
     if approved {
-        let total = job.amount.checked_add(job.provider_stake).ok_or(TrustNetError::InsufficientFunds)?;
-        let fee = job.amount
-            .checked_mul(PROTOCOL_FEE_BPS)
-            .and_then(|v| v.checked_div(BPS_DENOMINATOR))
-            .unwrap_or(0);
-        let payout = total.saturating_sub(fee);
+        release_to_provider(
+            job,
+            &escrow,
+            &ctx.accounts.provider,
+            &ctx.accounts.provider_token_account,
+            &ctx.accounts.treasury,
+            &ctx.accounts.treasury_token_account,
+        )?;
+        settle_reputations(
+            job,
+            &ctx.accounts.provider_reputation,
+            &ctx.accounts.client_reputation,
+            ctx.accounts.provider_category_reputation.as_deref_mut(),
+            true,
+            job.amount,
+            now_ts(&Clock::get()?),
+        )?;
+        return Ok(());
+    }
 
-        let job_key = job.key();
-        let signer_seeds = &[b"job_vault", job_key.as_ref(), &[*ctx.bumps.get("job_vault").unwrap()]];
+    apply_rejection(
+        job,
+        &escrow,
+        &ctx.accounts.client,
+        &ctx.accounts.client_token_account,
+        ctx.accounts.dispute.as_deref_mut(),
+        ctx.accounts.dispute_vault.as_deref(),
+        &ctx.accounts.dispute_token_vault,
+        notes_hash,
+    )?;
+    match job.status {
+        JobStatus::Disputed => {
+            if let Some(dispute) = ctx.accounts.dispute.as_mut() {
+                dispute.bump = *ctx.bumps.get("dispute").unwrap();
+            }
+        }
+        JobStatus::Failed => settle_reputations(
+            job,
+            &ctx.accounts.provider_reputation,
+            &ctx.accounts.client_reputation,
+            ctx.accounts.provider_category_reputation.as_deref_mut(),
+            false,
+            0,
+            now_ts(&Clock::get()?),
+        )?,
+        _ => {}
+    }
+    Ok(())
+}
 
-        let transfer_to_provider = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.job_vault.to_account_info(),
-                to: ctx.accounts.provider.to_account_info(),
-            },
-            &[signer_seeds],
-        );
-        system_program::transfer(transfer_to_provider, payout)?;
+/// Pays out an oracle-approved job: escrow plus stake to the provider, minus the protocol fee.
+pub fn release_to_provider<'info>(
+    job: &mut JobEscrow,
+    escrow: &Escrow<'_, 'info>,
+    provider: &AccountInfo<'info>,
+    provider_token_account: &Option<InterfaceAccount<'info, TokenAccount>>,
+    treasury: &AccountInfo<'info>,
+    treasury_token_account: &Option<InterfaceAccount<'info, TokenAccount>>,
+) -> Result<()> {
+    let total = job.amount.checked_add(job.provider_stake).ok_or(TrustNetError::InsufficientFunds)?;
+    let fee = job.amount
+        .checked_mul(PROTOCOL_FEE_BPS)
+        .and_then(|v| v.checked_div(BPS_DENOMINATOR))
+        .unwrap_or(0);
+    let payout = total.saturating_sub(fee);
 
-        if fee > 0 {
-            let transfer_fee = CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.job_vault.to_account_info(),
-                    to: ctx.accounts.treasury.to_account_info(),
-                },
-                &[signer_seeds],
-            );
-            system_program::transfer(transfer_fee, fee)?;
-        }
+    escrow.pay(provider, provider_token_account, payout)?;
+    escrow.pay(treasury, treasury_token_account, fee)?;
 
-        job.status = JobStatus::Completed;
-        job.completed_at = Some(now_ts(&Clock::get()?));
+    job.status = JobStatus::Completed;
+    job.completed_at = Some(now_ts(&Clock::get()?));
+    Ok(())
+}
+
+/// Applies the job's configured `OracleRejectAction` to an oracle rejection.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_rejection<'info>(
+    job: &mut Account<'info, JobEscrow>,
+    escrow: &Escrow<'_, 'info>,
+    client: &AccountInfo<'info>,
+    client_token_account: &Option<InterfaceAccount<'info, TokenAccount>>,
+    dispute: Option<&mut Dispute>,
+    dispute_vault: Option<&AccountInfo<'info>>,
+    dispute_token_vault: &Option<InterfaceAccount<'info, TokenAccount>>,
+    notes_hash: [u8; 32],
+) -> Result<()> {
+    match job.oracle_reject_action() {
+        OracleRejectAction::Rework => job.reopen_for_revision(now_ts(&Clock::get()?)),
+        OracleRejectAction::Refund => {
+            let total = job.amount.checked_add(job.provider_stake).ok_or(TrustNetError::InsufficientFunds)?;
+            escrow.pay(client, client_token_account, total)?;
+            job.status = JobStatus::Failed;
+            job.completed_at = Some(now_ts(&Clock::get()?));
+        }
+        OracleRejectAction::Dispute => {
+            let dispute = dispute.ok_or(TrustNetError::InvalidDisputeState)?;
+            let dispute_vault = dispute_vault.ok_or(TrustNetError::InvalidDisputeState)?;
+            escalate_to_dispute(job, dispute, notes_hash, [0u8; 32], escrow, dispute_vault, dispute_token_vault)?;
+        }
     }
     Ok(())
 }
@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::state::dispute::{Dispute, DisputeStatus};
-use crate::state::job_escrow::{JobEscrow, JobStatus};
-use crate::utils::constants::{BPS_DENOMINATOR, DISPUTE_FEE_BPS, now_ts};
+use crate::state::job_escrow::{JobEscrow, JobStatus, MilestoneStatus};
+use crate::utils::constants::{ARBITER_PANEL_SIZE, ARBITER_SELECTION_DELAY_SLOTS, BPS_DENOMINATOR, DISPUTE_FEE_BPS};
 use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::{Escrow, TokenVault};
 use crate::utils::hashing::hash_bytes;
 
 #[derive(Accounts)]
@@ -35,51 +36,219 @@ pub struct RaiseDispute<'info> {
         bump
     )]
     pub dispute_vault: SystemAccount<'info>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"job_token_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = raiser,
+        seeds = [b"dispute_token_vault", dispute.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = dispute_vault,
+        token::token_program = token_program
+    )]
+    pub dispute_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(milestone_index: u8)]
+pub struct RaiseMilestoneDispute<'info> {
+    #[account(mut)]
+    pub raiser: Signer<'info>,
+    #[account(mut)]
+    pub job: Account<'info, JobEscrow>,
+    #[account(
+        init,
+        payer = raiser,
+        space = Dispute::LEN,
+        seeds = [b"dispute", job.key().as_ref(), &[milestone_index]],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        mut,
+        seeds = [b"job_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_vault: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = raiser,
+        space = 0,
+        seeds = [b"dispute_vault", dispute.key().as_ref()],
+        bump
+    )]
+    pub dispute_vault: SystemAccount<'info>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"job_token_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = raiser,
+        seeds = [b"dispute_token_vault", dispute.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = dispute_vault,
+        token::token_program = token_program
+    )]
+    pub dispute_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<RaiseDispute>, reason_bytes: Vec<u8>, evidence_hash: [u8; 32]) -> Result<()> {
     let job = &mut ctx.accounts.job;
     require!(job.status == JobStatus::Submitted, TrustNetError::InvalidStatus);
+    require!(job.milestones.is_empty(), TrustNetError::InvalidMilestone);
+
+    let token = TokenVault::for_mint(
+        job.mint,
+        &ctx.accounts.job_token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+    let escrow = Escrow::new(b"job_vault", job.key(), &ctx.accounts.job_vault, &ctx.accounts.system_program, token);
+
     let dispute = &mut ctx.accounts.dispute;
+    escalate_to_dispute(
+        job,
+        dispute,
+        hash_bytes(&reason_bytes),
+        evidence_hash,
+        &escrow,
+        &ctx.accounts.dispute_vault,
+        &ctx.accounts.dispute_token_vault,
+    )?;
+    dispute.bump = *ctx.bumps.get("dispute").unwrap();
+    Ok(())
+}
 
-    let now = now_ts(&Clock::get()?);
-    let commit_deadline = now + 3600;
-    let reveal_deadline = commit_deadline + 3600;
+/// Opens a whole-job dispute: moves the dispute fee into the dispute vault and marks the job `Disputed`.
+pub fn escalate_to_dispute<'info>(
+    job: &mut Account<'info, JobEscrow>,
+    dispute: &mut Dispute,
+    reason_hash: [u8; 32],
+    evidence_hash: [u8; 32],
+    escrow: &Escrow<'_, 'info>,
+    dispute_vault: &AccountInfo<'info>,
+    dispute_token_vault: &Option<InterfaceAccount<'info, TokenAccount>>,
+) -> Result<()> {
+    open_dispute(dispute, job, reason_hash, evidence_hash, None)?;
+
+    let dispute_fee = job.amount
+        .checked_mul(DISPUTE_FEE_BPS)
+        .and_then(|v| v.checked_div(BPS_DENOMINATOR))
+        .unwrap_or(0);
+    escrow.pay(dispute_vault, dispute_token_vault, dispute_fee)?;
+    // The fee leaves the job vault, so the escrowed amount shrinks with it.
+    job.amount = job.amount.saturating_sub(dispute_fee);
+
+    job.status = JobStatus::Disputed;
+    Ok(())
+}
+
+pub fn milestone_handler(
+    ctx: Context<RaiseMilestoneDispute>,
+    milestone_index: u8,
+    reason_bytes: Vec<u8>,
+    evidence_hash: [u8; 32],
+) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Active, TrustNetError::InvalidStatus);
+    let raiser = ctx.accounts.raiser.key();
+    require!(raiser == job.client || raiser == job.provider, TrustNetError::Unauthorized);
+
+    let dispute = &mut ctx.accounts.dispute;
+    open_dispute(dispute, job, hash_bytes(&reason_bytes), evidence_hash, Some(milestone_index))?;
+    dispute.bump = *ctx.bumps.get("dispute").unwrap();
+
+    let milestone = job.milestones.get_mut(milestone_index as usize).ok_or(TrustNetError::InvalidMilestone)?;
+    require!(milestone.status == MilestoneStatus::Submitted, TrustNetError::InvalidStatus);
+    let dispute_fee = milestone.amount
+        .checked_mul(DISPUTE_FEE_BPS)
+        .and_then(|v| v.checked_div(BPS_DENOMINATOR))
+        .unwrap_or(0);
+    milestone.amount = milestone.amount.saturating_sub(dispute_fee);
+    milestone.status = MilestoneStatus::Disputed;
+    job.amount = job.amount.saturating_sub(dispute_fee);
+
+    let token = TokenVault::for_mint(
+        job.mint,
+        &ctx.accounts.job_token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+    let escrow = Escrow::new(b"job_vault", job.key(), &ctx.accounts.job_vault, &ctx.accounts.system_program, token);
+    escrow.pay(&ctx.accounts.dispute_vault, &ctx.accounts.dispute_token_vault, dispute_fee)?;
+    Ok(())
+}
+
+fn open_dispute(
+    dispute: &mut Dispute,
+    job: &Account<JobEscrow>,
+    reason_hash: [u8; 32],
+    evidence_hash: [u8; 32],
+    milestone: Option<u8>,
+) -> Result<()> {
+    // Arbiters judge the exact deliverable under dispute.
+    let (submission_index, submission_hash) = match milestone {
+        Some(index) => {
+            let m = job.milestones.get(index as usize).ok_or(TrustNetError::InvalidMilestone)?;
+            (m.submission_index.unwrap_or_default(), m.submission_hash.unwrap_or_default())
+        }
+        None => (job.latest_submission(), job.submission_hash.unwrap_or_default()),
+    };
 
     dispute.job = job.key();
     dispute.client = job.client;
     dispute.provider = job.provider;
-    dispute.reason_hash = hash_bytes(&reason_bytes);
+    dispute.reason_hash = reason_hash;
     dispute.evidence_hash = evidence_hash;
-    dispute.status = DisputeStatus::CommitPhase;
-    dispute.commit_deadline = commit_deadline;
-    dispute.reveal_deadline = reveal_deadline;
-    dispute.selected_arbiters = Vec::with_capacity(3);
+    // Voting opens once `select_arbiters` draws the panel.
+    dispute.status = DisputeStatus::Open;
+    dispute.commit_deadline = 0;
+    dispute.reveal_deadline = 0;
+    dispute.selected_arbiters = Vec::with_capacity(ARBITER_PANEL_SIZE);
     dispute.client_weight = 0;
     dispute.provider_weight = 0;
+    dispute.commit_count = 0;
+    dispute.reveal_count = 0;
     dispute.resolved_in_favor_of_client = None;
-    dispute.bump = *ctx.bumps.get("dispute").unwrap();
-
-    let dispute_fee = job.amount
-        .checked_mul(DISPUTE_FEE_BPS)
-        .and_then(|v| v.checked_div(BPS_DENOMINATOR))
-        .unwrap_or(0);
+    dispute.milestone = milestone;
+    dispute.submission_index = submission_index;
+    dispute.submission_hash = submission_hash;
+    dispute.selection_seed = [0u8; 32];
+    dispute.selection_slot = Clock::get()?.slot.saturating_add(ARBITER_SELECTION_DELAY_SLOTS);
+    Ok(())
+}
 
-    if dispute_fee > 0 {
-        let job_key = job.key();
-        let signer_seeds = &[b"job_vault", job_key.as_ref(), &[*ctx.bumps.get("job_vault").unwrap()]];
-        let transfer_fee = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.job_vault.to_account_info(),
-                to: ctx.accounts.dispute_vault.to_account_info(),
-            },
-            &[signer_seeds],
-        );
-        system_program::transfer(transfer_fee, dispute_fee)?;
-    }
+/// Seed helper for instructions that open a dispute only on some paths, where the
+/// dispute vaults hang off an optional dispute account.
+pub fn dispute_key(dispute: &Option<Account<Dispute>>) -> Pubkey {
+    dispute.as_ref().map(|d| d.key()).unwrap_or_default()
+}
 
-    job.status = JobStatus::Disputed;
+/// The optional dispute accounts are `init`, so passing them creates the job's dispute PDA.
+/// Paths that do not escalate must refuse them, or the PDA would block any later dispute.
+pub fn require_no_dispute_accounts<'info>(
+    dispute: &Option<Account<'info, Dispute>>,
+    dispute_vault: &Option<SystemAccount<'info>>,
+    dispute_token_vault: &Option<InterfaceAccount<'info, TokenAccount>>,
+) -> Result<()> {
+    require!(
+        dispute.is_none() && dispute_vault.is_none() && dispute_token_vault.is_none(),
+        TrustNetError::InvalidDisputeState
+    );
     Ok(())
 }
@@ -1,10 +1,10 @@
 use anchor_lang::prelude::*;
 
 use crate::state::job_escrow::{JobEscrow, JobStatus};
-use crate::state::reputation::{AgentReputation, Rating};
+use crate::state::reputation::{AgentReputation, CategoryReputation, Rating};
 use crate::utils::constants::MAX_TAGS_LEN;
 use crate::utils::errors::TrustNetError;
-use crate::utils::math::compute_rating_average;
+use crate::utils::math::rating_weight;
 use crate::utils::constants::now_ts;
 
 #[derive(Accounts)]
@@ -12,10 +12,28 @@ use crate::utils::constants::now_ts;
 pub struct RateJob<'info> {
     #[account(mut)]
     pub rater: Signer<'info>,
-    #[account(mut)]
+    #[account(
+        seeds = [b"job", job_id.as_ref()],
+        bump = job.bump
+    )]
     pub job: Account<'info, JobEscrow>,
+    /// Must belong to the rater's counterparty on the job.
     #[account(mut)]
     pub ratee_reputation: Account<'info, AgentReputation>,
+    /// Optional: weights the rating by the rater's own score.
+    #[account(
+        seeds = [b"reputation", rater.key().as_ref()],
+        bump
+    )]
+    pub rater_reputation: Option<Account<'info, AgentReputation>>,
+    /// Optional: the ratee's track for the job's category, if the job has one.
+    #[account(
+        mut,
+        seeds = [b"category_rep", ratee_reputation.agent.as_ref(), &[job.category.unwrap_or_default()]],
+        bump
+    )]
+    pub ratee_category_reputation: Option<Account<'info, CategoryReputation>>,
+    /// One rating per job and direction: the rater is either the client or the provider.
     #[account(
         init,
         payer = rater,
@@ -39,20 +57,35 @@ pub fn handler(
 
     let job = &ctx.accounts.job;
     require!(job.status == JobStatus::Completed || job.status == JobStatus::Resolved, TrustNetError::InvalidStatus);
+    let rater = ctx.accounts.rater.key();
+    let ratee = if rater == job.client {
+        job.provider
+    } else if rater == job.provider {
+        job.client
+    } else {
+        return Err(TrustNetError::Unauthorized.into());
+    };
+    require!(ratee != rater, TrustNetError::Unauthorized);
+    require!(ctx.accounts.ratee_reputation.agent == ratee, TrustNetError::Unauthorized);
 
     let rating = &mut ctx.accounts.rating;
     rating.job_id = job_id;
-    rating.rater = ctx.accounts.rater.key();
-    rating.ratee = ctx.accounts.ratee_reputation.agent;
+    rating.rater = rater;
+    rating.ratee = ratee;
     rating.score = score;
     rating.tags = tags;
     rating.comment_hash = comment_hash;
-    rating.timestamp = now_ts(&Clock::get()?);
+    let now = now_ts(&Clock::get()?);
+    rating.timestamp = now;
     rating.bump = *ctx.bumps.get("rating").unwrap();
 
-    let rep = &mut ctx.accounts.ratee_reputation;
-    rep.avg_rating = compute_rating_average(rep.avg_rating, rep.rating_count, score);
-    rep.rating_count = rep.rating_count.saturating_add(1);
-    rep.last_active = now_ts(&Clock::get()?);
+    let rater_score = ctx.accounts.rater_reputation.as_ref().map(|r| r.score(now).score);
+    let weight = rating_weight(job.lamport_value(job.amount), rater_score);
+    ctx.accounts.ratee_reputation.record_rating(score, weight, now);
+    if job.category.is_some() {
+        if let Some(track) = ctx.accounts.ratee_category_reputation.as_mut() {
+            track.record_rating(score, now);
+        }
+    }
     Ok(())
 }
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
-use crate::state::arbiter::Arbiter;
+use crate::state::arbiter::{Arbiter, ArbiterRegistry, ArbiterRegistryPage, RegistryEntry};
 use crate::utils::constants::{MIN_ARBITER_STAKE_LAMPORTS, now_ts, MAX_SPECIALIZATIONS_LEN};
 use crate::utils::errors::TrustNetError;
 
@@ -25,6 +25,19 @@ pub struct RegisterArbiter<'info> {
         bump
     )]
     pub arbiter_vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"arbiter_registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, ArbiterRegistry>,
+    /// Any registry page with room.
+    #[account(
+        mut,
+        seeds = [b"arbiter_registry_page", registry_page.index.to_le_bytes().as_ref()],
+        bump = registry_page.bump
+    )]
+    pub registry_page: Account<'info, ArbiterRegistryPage>,
     pub system_program: Program<'info, System>,
 }
 
@@ -41,8 +54,13 @@ pub fn handler(ctx: Context<RegisterArbiter>, stake_amount: u64, specializations
     arbiter.active = true;
     arbiter.created_at = now_ts(&Clock::get()?);
     arbiter.last_case = arbiter.created_at;
+    arbiter.registry_page = ctx.accounts.registry_page.index;
+    arbiter.open_cases = 0;
+    arbiter.withdrawable_at = 0;
     arbiter.bump = *ctx.bumps.get("arbiter").unwrap();
 
+    ctx.accounts.registry_page.insert(&mut ctx.accounts.registry, RegistryEntry::new(arbiter))?;
+
     let cpi = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
         system_program::Transfer {
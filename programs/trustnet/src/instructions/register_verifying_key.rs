@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::state::zk::VerifyingKey;
+use crate::utils::constants::MAX_PUBLIC_INPUTS;
+use crate::utils::errors::TrustNetError;
+
+#[derive(Accounts)]
+#[instruction(alpha_g1: [u8; 64], beta_g2: [u8; 128], gamma_g2: [u8; 128], delta_g2: [u8; 128], ic: Vec<[u8; 64]>)]
+pub struct RegisterVerifyingKey<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = VerifyingKey::LEN,
+        seeds = [b"zk_vk", VerifyingKey::compute_hash(&alpha_g1, &beta_g2, &gamma_g2, &delta_g2, &ic).as_ref()],
+        bump
+    )]
+    pub verifying_key: Account<'info, VerifyingKey>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<RegisterVerifyingKey>,
+    alpha_g1: [u8; 64],
+    beta_g2: [u8; 128],
+    gamma_g2: [u8; 128],
+    delta_g2: [u8; 128],
+    ic: Vec<[u8; 64]>,
+) -> Result<()> {
+    require!(!ic.is_empty() && ic.len() <= MAX_PUBLIC_INPUTS + 1, TrustNetError::InvalidVerifyingKey);
+
+    let vk = &mut ctx.accounts.verifying_key;
+    vk.hash = VerifyingKey::compute_hash(&alpha_g1, &beta_g2, &gamma_g2, &delta_g2, &ic);
+    vk.alpha_g1 = alpha_g1;
+    vk.beta_g2 = beta_g2;
+    vk.gamma_g2 = gamma_g2;
+    vk.delta_g2 = delta_g2;
+    vk.ic = ic;
+    vk.bump = *ctx.bumps.get("verifying_key").unwrap();
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::instructions::raise_dispute::{dispute_key, escalate_to_dispute, require_no_dispute_accounts};
+use crate::state::dispute::Dispute;
+use crate::state::job_escrow::{JobEscrow, JobStatus, VerifyType};
+use crate::utils::constants::now_ts;
+use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::{Escrow, TokenVault};
+
+#[derive(Accounts)]
+pub struct RejectSubmission<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+    #[account(mut, has_one = client @ TrustNetError::Unauthorized)]
+    pub job: Account<'info, JobEscrow>,
+    /// Only accepted once the revision cap is reached and the rejection opens a dispute.
+    #[account(
+        init,
+        payer = client,
+        space = Dispute::LEN,
+        seeds = [b"dispute", job.key().as_ref()],
+        bump
+    )]
+    pub dispute: Option<Account<'info, Dispute>>,
+    #[account(
+        mut,
+        seeds = [b"job_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_vault: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = client,
+        space = 0,
+        seeds = [b"dispute_vault", dispute_key(&dispute).as_ref()],
+        bump
+    )]
+    pub dispute_vault: Option<SystemAccount<'info>>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"job_token_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = client,
+        seeds = [b"dispute_token_vault", dispute_key(&dispute).as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = dispute_vault,
+        token::token_program = token_program
+    )]
+    pub dispute_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RejectSubmission>, feedback_hash: [u8; 32]) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Submitted, TrustNetError::InvalidStatus);
+    require!(job.verification_type == VerifyType::ClientApproval, TrustNetError::InvalidStatus);
+    require!(job.milestones.is_empty(), TrustNetError::InvalidMilestone);
+    job.feedback_hash = Some(feedback_hash);
+
+    if job.revision_count < job.max_revisions() {
+        require_no_dispute_accounts(
+            &ctx.accounts.dispute,
+            &ctx.accounts.dispute_vault,
+            &ctx.accounts.dispute_token_vault,
+        )?;
+        job.revision_count += 1;
+        job.reopen_for_revision(now_ts(&Clock::get()?));
+        return Ok(());
+    }
+
+    // Out of revision rounds: the rejection escalates to the arbiters.
+    let dispute = ctx.accounts.dispute.as_mut().ok_or(TrustNetError::InvalidDisputeState)?;
+    let dispute_vault = ctx.accounts.dispute_vault.as_ref().ok_or(TrustNetError::InvalidDisputeState)?;
+    let token = TokenVault::for_mint(
+        job.mint,
+        &ctx.accounts.job_token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+    let escrow = Escrow::new(b"job_vault", job.key(), &ctx.accounts.job_vault, &ctx.accounts.system_program, token);
+    escalate_to_dispute(
+        job,
+        dispute,
+        feedback_hash,
+        [0u8; 32],
+        &escrow,
+        dispute_vault,
+        &ctx.accounts.dispute_token_vault,
+    )?;
+    dispute.bump = *ctx.bumps.get("dispute").unwrap();
+    Ok(())
+}
@@ -1,16 +1,22 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::state::arbiter::Arbiter;
+use crate::instructions::approve_completion::finish_milestone_job;
+use crate::state::arbiter::{Arbiter, ArbiterRegistry, ArbiterRegistryPage};
 use crate::state::dispute::{Dispute, DisputeStatus, VoteCommitment};
-use crate::state::job_escrow::{JobEscrow, JobStatus};
-use crate::utils::constants::{BPS_DENOMINATOR, PROTOCOL_FEE_BPS, now_ts};
+use crate::state::job_escrow::{JobEscrow, JobStatus, MilestoneStatus};
+use crate::state::reputation::{load_reputation, record_settlement, store_reputation, AgentReputation, CategoryReputation};
+use crate::utils::constants::{
+    ARBITER_WITHDRAW_COOLDOWN_SECONDS, BPS_DENOMINATOR, MIN_ARBITER_STAKE_LAMPORTS, PROTOCOL_FEE_BPS, PROVIDER_SLASH_BPS,
+    SLASH_CLIENT_SHARE_BPS, now_ts,
+};
 use crate::utils::errors::TrustNetError;
-use crate::utils::math::integer_sqrt;
+use crate::utils::escrow::{transfer_from_vault, Escrow, TokenVault};
 
 #[derive(Accounts)]
 pub struct ResolveDispute<'info> {
-    #[account(mut)]
+    #[account(mut, has_one = job @ TrustNetError::Unauthorized)]
     pub dispute: Account<'info, Dispute>,
     #[account(mut)]
     pub job: Account<'info, JobEscrow>,
@@ -33,17 +39,86 @@ pub struct ResolveDispute<'info> {
     )]
     pub treasury: SystemAccount<'info>,
     /// CHECK: payout to client
-    #[account(mut)]
+    #[account(mut, address = job.client @ TrustNetError::Unauthorized)]
     pub client: UncheckedAccount<'info>,
     /// CHECK: payout to provider
-    #[account(mut)]
+    #[account(mut, address = job.provider @ TrustNetError::Unauthorized)]
     pub provider: UncheckedAccount<'info>,
+    /// CHECK: the provider's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.provider.as_ref()],
+        bump
+    )]
+    pub provider_reputation: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"rep_vault", job.provider.as_ref()],
+        bump
+    )]
+    pub provider_rep_vault: SystemAccount<'info>,
+    /// CHECK: the client's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.client.as_ref()],
+        bump
+    )]
+    pub client_reputation: UncheckedAccount<'info>,
+    /// Required when the job has a category.
+    #[account(
+        mut,
+        seeds = [b"category_rep", job.provider.as_ref(), &[job.category.unwrap_or_default()]],
+        bump
+    )]
+    pub provider_category_reputation: Option<Account<'info, CategoryReputation>>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"job_token_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"dispute_token_vault", dispute.key().as_ref()],
+        bump
+    )]
+    pub dispute_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub client_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub provider_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    /// Slashed arbiters' registry entries are kept in step with their stake.
+    #[account(
+        mut,
+        seeds = [b"arbiter_registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, ArbiterRegistry>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<ResolveDispute>) -> Result<()> {
+/// An arbiter's updated record after a resolution, reported as `ArbiterScored`.
+pub struct ArbiterScore {
+    pub arbiter: Pubkey,
+    pub correct: bool,
+    pub accuracy_score: u16,
+    pub cases_judged: u64,
+}
+
+/// Returns the lamports slashed from the provider's reputation stake, if the client won,
+/// and the updated score of every arbiter the case counted for. `remaining_accounts` holds
+/// `[arbiter, arbiter_vault, vote, reward_account, registry_page]` per arbiter, where
+/// `registry_page` is the page of the arbiter's registry entry. Every arbiter that committed
+/// a vote must be passed, so each has its open case released.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ResolveDispute<'info>>) -> Result<(u64, Vec<ArbiterScore>)> {
     let dispute = &mut ctx.accounts.dispute;
     require!(dispute.status == DisputeStatus::RevealPhase || dispute.status == DisputeStatus::CommitPhase, TrustNetError::InvalidDisputeState);
+    let now = now_ts(&Clock::get()?);
+    require!(dispute.voting_closed(now), TrustNetError::DeadlineNotReached);
 
     let total_weight = dispute.client_weight.saturating_add(dispute.provider_weight);
     let provider_ratio = if total_weight == 0 {
@@ -57,33 +132,54 @@ pub fn handler(ctx: Context<ResolveDispute>) -> Result<()> {
         dispute.client_weight * 100 / total_weight
     };
 
-    let now = now_ts(&Clock::get()?);
-    let mut provider_wins = if provider_ratio >= 60 {
-        true
-    } else if client_ratio >= 60 {
-        false
-    } else {
-        now > dispute.reveal_deadline
-    };
+    // Without a 60% supermajority for the client the provider is paid, however early or
+    // late the dispute is resolved, so the caller cannot pick the outcome by timing.
+    let provider_wins = provider_ratio >= 60 || client_ratio < 60;
 
     dispute.resolved_in_favor_of_client = Some(!provider_wins);
     dispute.status = DisputeStatus::Resolved;
 
     // Arbiter rewards + penalties
-    let dispute_fee_balance = ctx.accounts.dispute_vault.lamports();
+    let dispute_token = TokenVault::for_mint(
+        ctx.accounts.job.mint,
+        &ctx.accounts.dispute_token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+    let dispute_escrow = Escrow::new(
+        b"dispute_vault",
+        dispute.key(),
+        &ctx.accounts.dispute_vault,
+        &ctx.accounts.system_program,
+        dispute_token,
+    );
+    let dispute_fee_balance = dispute_escrow.balance();
     let mut winning_weight_total: u64 = 0;
     let mut arbiter_infos: Vec<(Pubkey, u64, bool)> = Vec::new();
+    let mut scores: Vec<ArbiterScore> = Vec::new();
 
+    // Check the whole arbiter set before anything is paid or slashed: each arbiter was drawn
+    // for this dispute, passed once, with its own vote PDA.
     let remaining = &ctx.remaining_accounts;
-    for chunk in remaining.chunks(4) {
-        if chunk.len() < 4 {
-            continue;
-        }
-        let arbiter_account = &chunk[0];
-        let vote_account = &chunk[2];
-        let arbiter: Account<Arbiter> = Account::try_from(arbiter_account)?;
-        let vote: Account<VoteCommitment> = Account::try_from(vote_account)?;
-        let weight = integer_sqrt(arbiter.stake);
+    require!(remaining.len() % 5 == 0, TrustNetError::InvalidArbiterSet);
+    let mut released: Vec<Pubkey> = Vec::new();
+    for chunk in remaining.chunks(5) {
+        let arbiter = chunk[0].key();
+        require!(dispute.selected_arbiters.contains(&arbiter), TrustNetError::ArbiterNotSelected);
+        require!(!released.contains(&arbiter), TrustNetError::InvalidArbiterSet);
+        let (vote_pda, _) =
+            Pubkey::find_program_address(&[b"vote", dispute.key().as_ref(), arbiter.as_ref()], &crate::ID);
+        require_keys_eq!(chunk[2].key(), vote_pda, TrustNetError::InvalidArbiterSet);
+        released.push(arbiter);
+    }
+
+    for chunk in remaining.chunks(5) {
+        let mut arbiter: Account<Arbiter> = Account::try_from(&chunk[0])?;
+        let vote: Account<VoteCommitment> = Account::try_from(&chunk[2])?;
+        arbiter.open_cases = arbiter.open_cases.saturating_sub(1);
+        // Stake added after the reveal must not buy a bigger share of the fee.
+        let weight = vote.weight;
+        let stake_before = arbiter.stake;
         let voted_provider = vote.vote.unwrap_or(false);
         if vote.revealed {
             let is_winner = voted_provider == provider_wins;
@@ -92,42 +188,121 @@ pub fn handler(ctx: Context<ResolveDispute>) -> Result<()> {
             }
             arbiter_infos.push((arbiter.key(), weight, is_winner));
         } else if now > dispute.reveal_deadline {
-            slash_arbiter(&arbiter, &chunk[1], &ctx.accounts.treasury, &ctx.accounts.system_program, 200)?;
+            slash_arbiter(&mut arbiter, &chunk[1], &ctx.accounts.treasury, &ctx.accounts.system_program, 200)?;
         }
         if vote.revealed && voted_provider != provider_wins {
-            slash_arbiter(&arbiter, &chunk[1], &ctx.accounts.treasury, &ctx.accounts.system_program, 100)?;
+            slash_arbiter(&mut arbiter, &chunk[1], &ctx.accounts.treasury, &ctx.accounts.system_program, 100)?;
+        }
+        if arbiter.stake != stake_before {
+            sync_registry_stake(&mut arbiter, &chunk[4], &mut ctx.accounts.registry, now)?;
+        }
+        // A vote left unrevealed only counts against the arbiter once the reveal window has closed.
+        if vote.revealed || now > dispute.reveal_deadline {
+            let correct = vote.revealed && voted_provider == provider_wins;
+            arbiter.record_case(correct, now);
+            scores.push(ArbiterScore {
+                arbiter: arbiter.key(),
+                correct,
+                accuracy_score: arbiter.accuracy_score,
+                cases_judged: arbiter.cases_judged,
+            });
         }
+        arbiter.exit(&crate::ID)?;
     }
+    require!(released.len() == dispute.commit_count as usize, TrustNetError::InvalidArbiterSet);
 
-    for chunk in remaining.chunks(4) {
-        if chunk.len() < 4 {
-            continue;
-        }
-        let arbiter_account = &chunk[0];
-        let arbiter: Account<Arbiter> = Account::try_from(arbiter_account)?;
-        let authority_account = &chunk[3];
-        let weight = integer_sqrt(arbiter.stake);
-        let is_winner = arbiter_infos
+    for chunk in remaining.chunks(5) {
+        let arbiter: Account<Arbiter> = Account::try_from(&chunk[0])?;
+        // Lamport disputes pay the authority wallet; token disputes pay its token account.
+        let reward_account = &chunk[3];
+        // Weight as revealed, before this case moved the accuracy score or the stake.
+        let (weight, is_winner) = arbiter_infos
             .iter()
             .find(|(key, _, _)| key == &arbiter.key())
-            .map(|(_, _, win)| *win)
-            .unwrap_or(false);
+            .map(|(_, weight, win)| (*weight, *win))
+            .unwrap_or((0, false));
         if is_winner && winning_weight_total > 0 {
             let share = dispute_fee_balance.saturating_mul(weight) / winning_weight_total;
-            if share > 0 {
-                transfer_from_vault(
-                    &ctx.accounts.dispute_vault,
-                    authority_account,
-                    &ctx.accounts.system_program,
-                    &[b"dispute_vault", ctx.accounts.dispute.key().as_ref()],
-                    share,
-                )?;
-            }
+            dispute_escrow.pay_to(reward_account, &arbiter.authority, share)?;
         }
     }
 
+    let mut provider_reputation = load_reputation(&ctx.accounts.provider_reputation)?;
+    let mut client_reputation = load_reputation(&ctx.accounts.client_reputation)?;
+    if ctx.accounts.job.provider_has_reputation {
+        require!(provider_reputation.is_some(), TrustNetError::MissingProviderReputation);
+    }
+    if let Some(provider_reputation) = provider_reputation.as_mut() {
+        provider_reputation.record_dispute(provider_wins, now);
+    }
+    if let Some(client_reputation) = client_reputation.as_mut() {
+        client_reputation.record_dispute(!provider_wins, now);
+    }
+    let slashed = if provider_wins {
+        0
+    } else {
+        slash_provider_stake(
+            provider_reputation.as_mut(),
+            PROVIDER_SLASH_BPS,
+            &ctx.accounts.provider_rep_vault,
+            &ctx.accounts.client,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?
+    };
+
     // Payouts
     let job = &mut ctx.accounts.job;
+    let job_token = TokenVault::for_mint(
+        job.mint,
+        &ctx.accounts.job_token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+    let escrow = Escrow::new(b"job_vault", job.key(), &ctx.accounts.job_vault, &ctx.accounts.system_program, job_token);
+
+    if let Some(index) = dispute.milestone {
+        require!(job.status == JobStatus::Active, TrustNetError::InvalidStatus);
+        let milestone = job.milestones.get_mut(index as usize).ok_or(TrustNetError::InvalidMilestone)?;
+        require!(milestone.status == MilestoneStatus::Disputed, TrustNetError::InvalidStatus);
+        let amount = milestone.amount;
+        if provider_wins {
+            milestone.status = MilestoneStatus::Released;
+            let fee = amount
+                .checked_mul(PROTOCOL_FEE_BPS)
+                .and_then(|v| v.checked_div(BPS_DENOMINATOR))
+                .unwrap_or(0);
+            escrow.pay(&ctx.accounts.provider, &ctx.accounts.provider_token_account, amount.saturating_sub(fee))?;
+            escrow.pay(&ctx.accounts.treasury, &ctx.accounts.treasury_token_account, fee)?;
+        } else {
+            milestone.status = MilestoneStatus::Refunded;
+            escrow.pay(&ctx.accounts.client, &ctx.accounts.client_token_account, amount)?;
+        }
+        finish_milestone_job(
+            job,
+            &escrow,
+            &ctx.accounts.client,
+            &ctx.accounts.client_token_account,
+            &ctx.accounts.provider,
+            &ctx.accounts.provider_token_account,
+            now,
+        )?;
+        if job.status == JobStatus::Completed {
+            record_settlement(
+                job,
+                provider_reputation.as_mut(),
+                client_reputation.as_mut(),
+                ctx.accounts.provider_category_reputation.as_deref_mut(),
+                job.fully_delivered(),
+                job.released_amount(),
+                now,
+            )?;
+        }
+        store_reputation(&ctx.accounts.provider_reputation, &provider_reputation)?;
+        store_reputation(&ctx.accounts.client_reputation, &client_reputation)?;
+        return Ok((slashed, scores));
+    }
+
     require!(job.status == JobStatus::Disputed, TrustNetError::InvalidStatus);
     let total = job.amount.checked_add(job.provider_stake).ok_or(TrustNetError::InsufficientFunds)?;
     if provider_wins {
@@ -136,60 +311,29 @@ pub fn handler(ctx: Context<ResolveDispute>) -> Result<()> {
             .and_then(|v| v.checked_div(BPS_DENOMINATOR))
             .unwrap_or(0);
         let payout = total.saturating_sub(fee);
-        transfer_from_vault(
-            &ctx.accounts.job_vault,
-            &ctx.accounts.provider,
-            &ctx.accounts.system_program,
-            &[b"job_vault", job.key().as_ref()],
-            payout,
-        )?;
-        if fee > 0 {
-            transfer_from_vault(
-                &ctx.accounts.job_vault,
-                &ctx.accounts.treasury,
-                &ctx.accounts.system_program,
-                &[b"job_vault", job.key().as_ref()],
-                fee,
-            )?;
-        }
+        escrow.pay(&ctx.accounts.provider, &ctx.accounts.provider_token_account, payout)?;
+        escrow.pay(&ctx.accounts.treasury, &ctx.accounts.treasury_token_account, fee)?;
     } else {
-        transfer_from_vault(
-            &ctx.accounts.job_vault,
-            &ctx.accounts.client,
-            &ctx.accounts.system_program,
-            &[b"job_vault", job.key().as_ref()],
-            total,
-        )?;
+        escrow.pay(&ctx.accounts.client, &ctx.accounts.client_token_account, total)?;
     }
 
     job.status = JobStatus::Resolved;
-    Ok(())
-}
-
-fn transfer_from_vault(
-    vault: &SystemAccount,
-    to: &AccountInfo,
-    system_program: &Program<System>,
-    seeds: &[&[u8]],
-    amount: u64,
-) -> Result<()> {
-    let (pda, bump) = Pubkey::find_program_address(seeds, &crate::ID);
-    let signer_seeds: &[&[u8]] = &[seeds[0], seeds[1], &[bump]];
-    let transfer = CpiContext::new_with_signer(
-        system_program.to_account_info(),
-        system_program::Transfer {
-            from: vault.to_account_info(),
-            to: to.clone(),
-        },
-        &[signer_seeds],
-    );
-    require!(pda == vault.key(), TrustNetError::Unauthorized);
-    system_program::transfer(transfer, amount)?;
-    Ok(())
+    record_settlement(
+        job,
+        provider_reputation.as_mut(),
+        client_reputation.as_mut(),
+        ctx.accounts.provider_category_reputation.as_deref_mut(),
+        provider_wins,
+        job.amount,
+        now,
+    )?;
+    store_reputation(&ctx.accounts.provider_reputation, &provider_reputation)?;
+    store_reputation(&ctx.accounts.client_reputation, &client_reputation)?;
+    Ok((slashed, scores))
 }
 
 fn slash_arbiter(
-    arbiter: &Account<Arbiter>,
+    arbiter: &mut Account<Arbiter>,
     vault_info: &AccountInfo,
     treasury: &SystemAccount,
     system_program: &Program<System>,
@@ -212,5 +356,55 @@ fn slash_arbiter(
         &[signer_seeds],
     );
     system_program::transfer(transfer, amount)?;
+    arbiter.stake = arbiter.stake.saturating_sub(amount);
     Ok(())
 }
+
+/// Mirrors a slashed arbiter's stake into its registry entry, or takes the arbiter out of
+/// the registry and starts its withdrawal cooldown once the stake is below the minimum.
+fn sync_registry_stake<'info>(
+    arbiter: &mut Account<Arbiter>,
+    page_info: &'info AccountInfo<'info>,
+    registry: &mut ArbiterRegistry,
+    now: i64,
+) -> Result<()> {
+    // A deactivated arbiter has already left the registry.
+    if !arbiter.active {
+        return Ok(());
+    }
+    let mut page: Account<ArbiterRegistryPage> = Account::try_from(page_info)?;
+    require!(page.index == arbiter.registry_page, TrustNetError::InvalidArbiterSet);
+    if arbiter.stake < MIN_ARBITER_STAKE_LAMPORTS {
+        page.remove(registry, &arbiter.key())?;
+        arbiter.active = false;
+        arbiter.withdrawable_at = now.saturating_add(ARBITER_WITHDRAW_COOLDOWN_SECONDS);
+    } else {
+        page.set_stake(registry, &arbiter.key(), arbiter.stake)?;
+    }
+    page.exit(&crate::ID)
+}
+
+/// Slashes `bps` of the provider's reputation stake, `PROVIDER_SLASH_BPS` for a whole job.
+/// `SLASH_CLIENT_SHARE_BPS` of it compensates the client and the rest goes to the treasury.
+/// Returns the lamports slashed, which is zero for a provider without a reputation profile.
+pub fn slash_provider_stake<'info>(
+    reputation: Option<&mut AgentReputation>,
+    bps: u64,
+    rep_vault: &AccountInfo<'info>,
+    client: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<u64> {
+    let Some(reputation) = reputation else {
+        return Ok(0);
+    };
+    let amount = reputation.slash(bps);
+    if amount == 0 {
+        return Ok(0);
+    }
+    let client_share = (amount as u128 * SLASH_CLIENT_SHARE_BPS as u128 / BPS_DENOMINATOR as u128) as u64;
+    let seeds: &[&[u8]] = &[b"rep_vault", reputation.agent.as_ref()];
+    transfer_from_vault(rep_vault, client, system_program, seeds, client_share)?;
+    transfer_from_vault(rep_vault, treasury, system_program, seeds, amount - client_share)?;
+    Ok(amount)
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::state::bid::Bid;
+use crate::state::job_escrow::{JobEscrow, JobStatus};
+use crate::utils::constants::now_ts;
+use crate::utils::errors::TrustNetError;
+use crate::utils::hashing::hash_bid;
+
+#[derive(Accounts)]
+pub struct RevealBid<'info> {
+    pub bidder: Signer<'info>,
+    #[account(mut)]
+    pub job: Account<'info, JobEscrow>,
+    #[account(
+        mut,
+        seeds = [b"bid", job.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+}
+
+pub fn handler(ctx: Context<RevealBid>, amount: u64, salt: Vec<u8>) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Created, TrustNetError::InvalidStatus);
+    let job_key = job.key();
+    let escrowed = job.amount;
+
+    let now = now_ts(&Clock::get()?);
+    let auction = job.auction.as_mut().ok_or(TrustNetError::BiddingClosed)?;
+    require!(
+        now > auction.commit_deadline && now <= auction.reveal_deadline,
+        TrustNetError::BiddingClosed
+    );
+
+    let bid = &mut ctx.accounts.bid;
+    require!(!bid.revealed, TrustNetError::InvalidBidReveal);
+    let expected = hash_bid(&bid.bidder, &job_key, amount, &salt);
+    require!(expected == bid.commit_hash, TrustNetError::InvalidBidReveal);
+    require!(amount > 0 && amount <= escrowed, TrustNetError::InvalidBidReveal);
+
+    bid.revealed = true;
+    bid.amount = Some(amount);
+
+    // Ties go to the bid revealed first.
+    if auction.lowest_bidder == Pubkey::default() || amount < auction.lowest_amount {
+        auction.lowest_bidder = bid.bidder;
+        auction.lowest_amount = amount;
+    }
+    Ok(())
+}
@@ -2,9 +2,9 @@ use anchor_lang::prelude::*;
 
 use crate::state::arbiter::Arbiter;
 use crate::state::dispute::{Dispute, DisputeStatus, VoteCommitment};
+use crate::utils::constants::now_ts;
 use crate::utils::errors::TrustNetError;
 use crate::utils::hashing::hash_vote;
-use crate::utils::math::integer_sqrt;
 
 #[derive(Accounts)]
 pub struct RevealVote<'info> {
@@ -24,12 +24,16 @@ pub struct RevealVote<'info> {
 pub fn handler(ctx: Context<RevealVote>, vote_bool: bool, salt: Vec<u8>) -> Result<()> {
     let dispute = &mut ctx.accounts.dispute;
     require!(dispute.status == DisputeStatus::CommitPhase || dispute.status == DisputeStatus::RevealPhase, TrustNetError::InvalidDisputeState);
+    let now = now_ts(&Clock::get()?);
+    require!(dispute.reveals_open(now), TrustNetError::DeadlineNotReached);
+    require!(now <= dispute.reveal_deadline, TrustNetError::RevealPhaseClosed);
 
     let vote = &mut ctx.accounts.vote_commitment;
+    require!(!vote.revealed, TrustNetError::InvalidVoteReveal);
     let expected = hash_vote(&ctx.accounts.arbiter.key(), &dispute.key(), vote_bool, &salt);
     require!(expected == vote.commit_hash, TrustNetError::InvalidVoteReveal);
 
-    let weight = integer_sqrt(ctx.accounts.arbiter.stake);
+    let weight = ctx.accounts.arbiter.vote_weight();
     if vote_bool {
         dispute.provider_weight = dispute.provider_weight.saturating_add(weight);
     } else {
@@ -38,6 +42,8 @@ pub fn handler(ctx: Context<RevealVote>, vote_bool: bool, salt: Vec<u8>) -> Resu
 
     vote.revealed = true;
     vote.vote = Some(vote_bool);
+    vote.weight = weight;
+    dispute.reveal_count = dispute.reveal_count.saturating_add(1);
     dispute.status = DisputeStatus::RevealPhase;
     Ok(())
 }
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+
+use crate::state::arbiter::{ArbiterRegistry, ArbiterRegistryPage};
+use crate::state::dispute::{Dispute, DisputeStatus};
+use crate::state::job_escrow::JobEscrow;
+use crate::utils::constants::{
+    ARBITER_PANEL_SIZE, ARBITER_SELECTION_DELAY_SLOTS, DISPUTE_COMMIT_SECONDS, DISPUTE_REVEAL_SECONDS, now_ts,
+};
+use crate::utils::errors::TrustNetError;
+use crate::utils::hashing::hash_bytes;
+
+#[derive(Accounts)]
+pub struct SelectArbiters<'info> {
+    #[account(mut)]
+    pub dispute: Account<'info, Dispute>,
+    #[account(address = dispute.job @ TrustNetError::Unauthorized)]
+    pub job: Account<'info, JobEscrow>,
+    #[account(
+        seeds = [b"arbiter_registry"],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, ArbiterRegistry>,
+    /// CHECK: address-checked; searched for the hash of `dispute.selection_slot`
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+/// Draws the dispute's arbiter panel and opens the commit phase. Permissionless.
+/// `remaining_accounts` must be every registry page, in index order, so the caller
+/// cannot choose the candidates. Arbiters that are not a party to the job and, for a
+/// categorized job, specialize in its category are drawn weighted by stake.
+///
+/// The draw is seeded by the hash of `dispute.selection_slot`, so it can run only once
+/// that slot has passed. If nobody drew while its hash was still in the sysvar, the call
+/// instead moves `selection_slot` to a new future slot and leaves the dispute open.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SelectArbiters<'info>>) -> Result<()> {
+    let dispute = &mut ctx.accounts.dispute;
+    require!(dispute.status == DisputeStatus::Open, TrustNetError::InvalidDisputeState);
+    let registry = &ctx.accounts.registry;
+    require!(ctx.remaining_accounts.len() == registry.page_count as usize, TrustNetError::InvalidArbiterSet);
+
+    let category = ctx.accounts.job.category;
+    let client_arbiter = Pubkey::find_program_address(&[b"arbiter", dispute.client.as_ref()], &crate::ID).0;
+    let provider_arbiter = Pubkey::find_program_address(&[b"arbiter", dispute.provider.as_ref()], &crate::ID).0;
+    let mut candidates: Vec<(Pubkey, u64)> = Vec::new();
+    for (index, info) in ctx.remaining_accounts.iter().enumerate() {
+        let page: Account<ArbiterRegistryPage> = Account::try_from(info)?;
+        require!(page.index as usize == index, TrustNetError::InvalidArbiterSet);
+        for entry in page.entries.iter() {
+            let is_party = entry.arbiter == client_arbiter || entry.arbiter == provider_arbiter;
+            let specialized = category.map_or(true, |c| entry.specializes_in(c));
+            if !is_party && specialized && entry.stake > 0 {
+                candidates.push((entry.arbiter, entry.stake));
+            }
+        }
+    }
+    require!(!candidates.is_empty(), TrustNetError::NoEligibleArbiters);
+
+    let clock = Clock::get()?;
+    let current_slot = clock.slot;
+    require!(current_slot > dispute.selection_slot, TrustNetError::SelectionSlotPending);
+    let Some(seed) = selection_seed(&ctx.accounts.slot_hashes, dispute.selection_slot, &dispute.key())? else {
+        dispute.selection_slot = current_slot.saturating_add(ARBITER_SELECTION_DELAY_SLOTS);
+        return Ok(());
+    };
+    dispute.selected_arbiters = draw_panel(candidates, &seed, ARBITER_PANEL_SIZE);
+    dispute.selection_seed = seed;
+
+    let now = now_ts(&clock);
+    dispute.commit_deadline = now + DISPUTE_COMMIT_SECONDS;
+    dispute.reveal_deadline = dispute.commit_deadline + DISPUTE_REVEAL_SECONDS;
+    dispute.status = DisputeStatus::CommitPhase;
+    Ok(())
+}
+
+/// Mixes the hash of `target` with the dispute key. A skipped slot has no hash, so the
+/// first slot after it stands in. Returns `None` once `target` has aged out of the sysvar,
+/// as the stand-in would then depend on when the draw runs.
+fn selection_seed(slot_hashes: &AccountInfo, target: u64, dispute: &Pubkey) -> Result<Option<[u8; 32]>> {
+    let data = slot_hashes.try_borrow_data()?;
+    // Layout: u64 entry count, then (u64 slot, [u8; 32] hash) entries, newest first.
+    let count = u64::from_le_bytes(data.get(..8).ok_or(TrustNetError::InvalidArbiterSet)?.try_into().unwrap());
+    let mut found: Option<(u64, &[u8])> = None;
+    let mut covered = false;
+    for entry in data[8..].chunks_exact(40).take(count as usize) {
+        let slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+        if slot < target {
+            covered = true;
+            break;
+        }
+        found = Some((slot, &entry[8..]));
+    }
+    let (slot, hash) = found.ok_or(TrustNetError::SelectionSlotPending)?;
+    if !covered && slot != target {
+        return Ok(None);
+    }
+    let mut input = Vec::with_capacity(64);
+    input.extend_from_slice(hash);
+    input.extend_from_slice(dispute.as_ref());
+    Ok(Some(hash_bytes(&input)))
+}
+
+/// Stake-weighted draw without replacement. Each round hashes the seed with the round
+/// number, so anyone can recompute the panel from `Dispute::selection_seed`.
+fn draw_panel(mut candidates: Vec<(Pubkey, u64)>, seed: &[u8; 32], size: usize) -> Vec<Pubkey> {
+    let mut panel = Vec::with_capacity(size);
+    let mut round: u8 = 0;
+    while panel.len() < size && !candidates.is_empty() {
+        let total: u128 = candidates.iter().map(|(_, stake)| *stake as u128).sum();
+        let mut input = seed.to_vec();
+        input.push(round);
+        let draw = hash_bytes(&input);
+        let mut target = u128::from_le_bytes(draw[..16].try_into().unwrap()) % total;
+        let index = candidates
+            .iter()
+            .position(|(_, stake)| {
+                if target < *stake as u128 {
+                    true
+                } else {
+                    target -= *stake as u128;
+                    false
+                }
+            })
+            .unwrap_or(0);
+        panel.push(candidates.remove(index).0);
+        round = round.saturating_add(1);
+    }
+    panel
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::state::job_escrow::{JobEscrow, JobStatus};
+use crate::utils::errors::TrustNetError;
+
+#[derive(Accounts)]
+pub struct SetJobCategory<'info> {
+    pub client: Signer<'info>,
+    #[account(mut, has_one = client @ TrustNetError::Unauthorized)]
+    pub job: Account<'info, JobEscrow>,
+}
+
+pub fn handler(ctx: Context<SetJobCategory>, category: u8) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Created, TrustNetError::InvalidStatus);
+    job.category = Some(category);
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::state::job_escrow::{JobEscrow, JobStatus, ProviderRequirements};
+use crate::utils::constants::MAX_REQUIRED_SPECIALIZATIONS;
+use crate::utils::errors::TrustNetError;
+
+#[derive(Accounts)]
+pub struct SetJobRequirements<'info> {
+    pub client: Signer<'info>,
+    #[account(mut, has_one = client @ TrustNetError::Unauthorized)]
+    pub job: Account<'info, JobEscrow>,
+}
+
+pub fn handler(ctx: Context<SetJobRequirements>, requirements: ProviderRequirements) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Created, TrustNetError::InvalidStatus);
+    require!(requirements.min_reputation_score <= 100, TrustNetError::RatingOutOfRange);
+    require!(
+        requirements.specializations.len() <= MAX_REQUIRED_SPECIALIZATIONS,
+        TrustNetError::BytesTooLarge
+    );
+    job.requirements = requirements;
+    Ok(())
+}
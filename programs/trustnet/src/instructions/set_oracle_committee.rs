@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::state::job_escrow::{JobEscrow, JobStatus, VerifyType};
+use crate::state::oracle::OracleCommittee;
+use crate::utils::constants::MAX_ORACLES;
+use crate::utils::errors::TrustNetError;
+
+#[derive(Accounts)]
+pub struct SetOracleCommittee<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+    #[account(has_one = client @ TrustNetError::Unauthorized)]
+    pub job: Account<'info, JobEscrow>,
+    #[account(
+        init,
+        payer = client,
+        space = OracleCommittee::LEN,
+        seeds = [b"oracle_committee", job.key().as_ref()],
+        bump
+    )]
+    pub committee: Account<'info, OracleCommittee>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetOracleCommittee>, oracles: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    let job = &ctx.accounts.job;
+    require!(job.status == JobStatus::Created, TrustNetError::InvalidStatus);
+    require!(job.verification_type == VerifyType::OracleThreshold, TrustNetError::InvalidStatus);
+    require!(!oracles.is_empty() && oracles.len() <= MAX_ORACLES, TrustNetError::InvalidOracleCommittee);
+    require!(threshold > 0 && threshold as usize <= oracles.len(), TrustNetError::InvalidOracleCommittee);
+    for (i, oracle) in oracles.iter().enumerate() {
+        require!(!oracles[..i].contains(oracle), TrustNetError::InvalidOracleCommittee);
+    }
+
+    let committee = &mut ctx.accounts.committee;
+    committee.job = job.key();
+    committee.oracles = oracles;
+    committee.threshold = threshold;
+    committee.approvals = 0;
+    committee.rejections = 0;
+    committee.round = 0;
+    committee.bump = *ctx.bumps.get("committee").unwrap();
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::state::job_escrow::{JobEscrow, JobStatus, VerifyType};
+use crate::state::review::ReviewPanel;
+use crate::utils::constants::MAX_REVIEWERS;
+use crate::utils::errors::TrustNetError;
+
+#[derive(Accounts)]
+pub struct SetReviewPanel<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+    #[account(has_one = client @ TrustNetError::Unauthorized)]
+    pub job: Account<'info, JobEscrow>,
+    #[account(
+        init,
+        payer = client,
+        space = ReviewPanel::LEN,
+        seeds = [b"review_panel", job.key().as_ref()],
+        bump
+    )]
+    pub panel: Account<'info, ReviewPanel>,
+    #[account(
+        init,
+        payer = client,
+        space = 0,
+        seeds = [b"review_vault", job.key().as_ref()],
+        bump
+    )]
+    pub review_vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// `reviewers` names the panel; leave it empty to open `seats` seats to staked reputation
+/// holders with `specialization`.
+pub fn handler(
+    ctx: Context<SetReviewPanel>,
+    reviewers: Vec<Pubkey>,
+    seats: u8,
+    specialization: Option<u8>,
+    bond: u64,
+) -> Result<()> {
+    let job = &ctx.accounts.job;
+    require!(job.status == JobStatus::Created, TrustNetError::InvalidStatus);
+    require!(job.verification_type == VerifyType::PeerReview, TrustNetError::InvalidStatus);
+    // Reviewer bonds and rewards are settled in lamports.
+    require!(job.mint.is_none(), TrustNetError::InvalidReviewPanel);
+
+    let named = !reviewers.is_empty();
+    let seats = if named { reviewers.len() } else { seats as usize };
+    require!(seats > 0 && seats <= MAX_REVIEWERS, TrustNetError::InvalidReviewPanel);
+    // An odd panel cannot split evenly, so a full panel always reaches a majority.
+    require!(seats % 2 == 1, TrustNetError::InvalidReviewPanel);
+    for (i, reviewer) in reviewers.iter().enumerate() {
+        require!(
+            !reviewers[..i].contains(reviewer) && *reviewer != job.client && *reviewer != job.provider,
+            TrustNetError::InvalidReviewPanel
+        );
+    }
+
+    let panel = &mut ctx.accounts.panel;
+    panel.job = job.key();
+    panel.named = named;
+    panel.reviewers = reviewers;
+    panel.seats = seats as u8;
+    panel.specialization = specialization;
+    panel.bond = bond;
+    panel.approvals = 0;
+    panel.rejections = 0;
+    panel.fee = 0;
+    panel.outcome = None;
+    panel.bump = *ctx.bumps.get("panel").unwrap();
+    Ok(())
+}
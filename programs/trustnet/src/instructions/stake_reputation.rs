@@ -2,6 +2,8 @@ use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
 use crate::state::reputation::AgentReputation;
+use crate::utils::constants::MIN_REPUTATION_STAKE_LAMPORTS;
+use crate::utils::errors::TrustNetError;
 
 #[derive(Accounts)]
 pub struct StakeReputation<'info> {
@@ -21,6 +23,10 @@ pub struct StakeReputation<'info> {
 pub fn handler(ctx: Context<StakeReputation>, add_lamports: u64) -> Result<()> {
     let reputation = &mut ctx.accounts.reputation;
     reputation.stake_amount = reputation.stake_amount.saturating_add(add_lamports);
+    if reputation.closed {
+        require!(reputation.stake_amount >= MIN_REPUTATION_STAKE_LAMPORTS, TrustNetError::MinimumStakeNotMet);
+        reputation.closed = false;
+    }
 
     let cpi = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
@@ -1,20 +1,76 @@
 use anchor_lang::prelude::*;
 
-use crate::state::job_escrow::{JobEscrow, JobStatus};
+use crate::state::job_escrow::{JobEscrow, JobStatus, MilestoneStatus, VerifyType};
+use crate::state::submission::Submission;
 use crate::utils::constants::now_ts;
 use crate::utils::errors::TrustNetError;
 
 #[derive(Accounts)]
 pub struct SubmitCompletion<'info> {
+    #[account(mut)]
     pub provider: Signer<'info>,
     #[account(mut, has_one = provider @ TrustNetError::Unauthorized)]
     pub job: Account<'info, JobEscrow>,
+    #[account(
+        init,
+        payer = provider,
+        space = Submission::LEN,
+        seeds = [b"submission", job.key().as_ref(), &job.submission_count.to_le_bytes()],
+        bump
+    )]
+    pub submission: Account<'info, Submission>,
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<SubmitCompletion>, _submission_hash: [u8; 32]) -> Result<()> {
+pub fn handler(ctx: Context<SubmitCompletion>, submission_hash: [u8; 32]) -> Result<()> {
     let job = &mut ctx.accounts.job;
     require!(job.status == JobStatus::Active, TrustNetError::InvalidStatus);
+    require!(job.milestones.is_empty(), TrustNetError::InvalidMilestone);
+    // ZkVerify jobs complete through `submit_zk_proof`.
+    require!(job.verification_type != VerifyType::ZkVerify, TrustNetError::InvalidStatus);
+    let now = now_ts(&Clock::get()?);
     job.status = JobStatus::Submitted;
-    job.submitted_at = Some(now_ts(&Clock::get()?));
+    job.submitted_at = Some(now);
+    job.submission_hash = Some(submission_hash);
+
+    let submission = &mut ctx.accounts.submission;
+    record_submission(submission, job, None, submission_hash, now)?;
+    submission.bump = *ctx.bumps.get("submission").unwrap();
+    Ok(())
+}
+
+pub fn milestone_handler(ctx: Context<SubmitCompletion>, milestone_index: u8, submission_hash: [u8; 32]) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Active, TrustNetError::InvalidStatus);
+    let now = now_ts(&Clock::get()?);
+    let index = job.submission_count;
+    let milestone = job.milestones.get_mut(milestone_index as usize).ok_or(TrustNetError::InvalidMilestone)?;
+    require!(milestone.status == MilestoneStatus::Pending, TrustNetError::InvalidStatus);
+    milestone.status = MilestoneStatus::Submitted;
+    milestone.submitted_at = Some(now);
+    milestone.submission_index = Some(index);
+    milestone.submission_hash = Some(submission_hash);
+    job.submitted_at = Some(now);
+
+    let submission = &mut ctx.accounts.submission;
+    record_submission(submission, job, Some(milestone_index), submission_hash, now)?;
+    submission.bump = *ctx.bumps.get("submission").unwrap();
+    Ok(())
+}
+
+fn record_submission(
+    submission: &mut Submission,
+    job: &mut Account<JobEscrow>,
+    milestone: Option<u8>,
+    submission_hash: [u8; 32],
+    now: i64,
+) -> Result<()> {
+    submission.job = job.key();
+    submission.provider = job.provider;
+    submission.index = job.submission_count;
+    submission.milestone = milestone;
+    submission.submission_hash = submission_hash;
+    submission.submitted_at = now;
+    job.submission_count = job.submission_count.checked_add(1).ok_or(TrustNetError::InvalidStatus)?;
     Ok(())
 }
@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::state::job_escrow::{JobEscrow, JobStatus, VerifyType};
+use crate::state::reputation::{settle_reputations, AgentReputation, CategoryReputation};
+use crate::state::review::{Review, ReviewPanel};
+use crate::utils::constants::{BPS_DENOMINATOR, PROTOCOL_FEE_BPS, REVIEW_PASS_SCORE, now_ts};
+use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::transfer_from_vault;
+
+#[derive(Accounts)]
+pub struct SubmitReview<'info> {
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+    #[account(mut)]
+    pub job: Account<'info, JobEscrow>,
+    #[account(
+        mut,
+        seeds = [b"review_panel", job.key().as_ref()],
+        bump
+    )]
+    pub panel: Account<'info, ReviewPanel>,
+    #[account(
+        init,
+        payer = reviewer,
+        space = Review::LEN,
+        seeds = [b"review", job.key().as_ref(), reviewer.key().as_ref()],
+        bump
+    )]
+    pub review: Account<'info, Review>,
+    /// Required to take a seat on an open panel, open and holding the minimum stake.
+    #[account(
+        seeds = [b"reputation", reviewer.key().as_ref()],
+        bump
+    )]
+    pub reviewer_reputation: Option<Account<'info, AgentReputation>>,
+    /// CHECK: the provider's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.provider.as_ref()],
+        bump
+    )]
+    pub provider_reputation: UncheckedAccount<'info>,
+    /// CHECK: the client's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.client.as_ref()],
+        bump
+    )]
+    pub client_reputation: UncheckedAccount<'info>,
+    /// Required when the job has a category.
+    #[account(
+        mut,
+        seeds = [b"category_rep", job.provider.as_ref(), &[job.category.unwrap_or_default()]],
+        bump
+    )]
+    pub provider_category_reputation: Option<Account<'info, CategoryReputation>>,
+    #[account(
+        mut,
+        seeds = [b"review_vault", job.key().as_ref()],
+        bump
+    )]
+    pub review_vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"job_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_vault: SystemAccount<'info>,
+    /// CHECK: provider receives payout
+    #[account(mut, address = job.provider @ TrustNetError::Unauthorized)]
+    pub provider: UncheckedAccount<'info>,
+    /// CHECK: client receives the refund if the panel rejects
+    #[account(mut, address = job.client @ TrustNetError::Unauthorized)]
+    pub client: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SubmitReview>, score: u8, comment_hash: [u8; 32]) -> Result<()> {
+    require!(score >= 1 && score <= 5, TrustNetError::RatingOutOfRange);
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Submitted, TrustNetError::InvalidStatus);
+    require!(job.verification_type == VerifyType::PeerReview, TrustNetError::InvalidStatus);
+
+    let reviewer = ctx.accounts.reviewer.key();
+    require!(reviewer != job.client && reviewer != job.provider, TrustNetError::ReviewerNotEligible);
+    let panel = &mut ctx.accounts.panel;
+    if panel.named {
+        require!(panel.reviewers.contains(&reviewer), TrustNetError::ReviewerNotEligible);
+    } else {
+        require!((panel.reviewers.len() as u8) < panel.seats, TrustNetError::ReviewerNotEligible);
+        let reputation = ctx.accounts.reviewer_reputation.as_ref().ok_or(TrustNetError::ReviewerNotEligible)?;
+        // A closed or unstaked profile has nothing at stake to vouch for the review.
+        require!(reputation.in_good_standing(), TrustNetError::ReviewerNotEligible);
+        if let Some(specialization) = panel.specialization {
+            require!(reputation.specializations.contains(&specialization), TrustNetError::ReviewerNotEligible);
+        }
+        panel.reviewers.push(reviewer);
+    }
+
+    let cpi = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.reviewer.to_account_info(),
+            to: ctx.accounts.review_vault.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi, panel.bond)?;
+
+    let now = now_ts(&Clock::get()?);
+    let approved = score >= REVIEW_PASS_SCORE;
+    let review = &mut ctx.accounts.review;
+    review.job = job.key();
+    review.reviewer = reviewer;
+    review.score = score;
+    review.approved = approved;
+    review.comment_hash = comment_hash;
+    review.claimed = false;
+    review.timestamp = now;
+    review.bump = *ctx.bumps.get("review").unwrap();
+
+    if approved {
+        panel.approvals = panel.approvals.saturating_add(1);
+    } else {
+        panel.rejections = panel.rejections.saturating_add(1);
+    }
+    let outcome = if panel.approvals >= panel.majority() {
+        true
+    } else if panel.rejections >= panel.majority() {
+        false
+    } else {
+        return Ok(());
+    };
+
+    // Majority reached: the protocol fee pays the reviewers instead of the treasury.
+    let total = job.amount.checked_add(job.provider_stake).ok_or(TrustNetError::InsufficientFunds)?;
+    let fee = job.amount
+        .checked_mul(PROTOCOL_FEE_BPS)
+        .and_then(|v| v.checked_div(BPS_DENOMINATOR))
+        .unwrap_or(0);
+    let job_key = job.key();
+    let seeds: &[&[u8]] = &[b"job_vault", job_key.as_ref()];
+    let vault = ctx.accounts.job_vault.to_account_info();
+    transfer_from_vault(&vault, &ctx.accounts.review_vault.to_account_info(), &ctx.accounts.system_program, seeds, fee)?;
+    panel.fee = fee;
+    panel.outcome = Some(outcome);
+
+    if outcome {
+        transfer_from_vault(&vault, &ctx.accounts.provider.to_account_info(), &ctx.accounts.system_program, seeds, total.saturating_sub(fee))?;
+        job.status = JobStatus::Completed;
+    } else {
+        transfer_from_vault(&vault, &ctx.accounts.client.to_account_info(), &ctx.accounts.system_program, seeds, total.saturating_sub(fee))?;
+        job.status = JobStatus::Failed;
+    }
+    job.completed_at = Some(now);
+    settle_reputations(
+        job,
+        &ctx.accounts.provider_reputation,
+        &ctx.accounts.client_reputation,
+        ctx.accounts.provider_category_reputation.as_deref_mut(),
+        outcome,
+        job.amount,
+        now,
+    )
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::instructions::oracle_verify::release_to_provider;
+use crate::state::job_escrow::{JobEscrow, JobStatus, VerifyType};
+use crate::state::reputation::{settle_reputations, CategoryReputation};
+use crate::state::zk::VerifyingKey;
+use crate::utils::constants::now_ts;
+use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::{Escrow, TokenVault};
+use crate::utils::groth16::{self, Groth16Proof};
+use crate::utils::hashing::zk_job_binding;
+
+#[derive(Accounts)]
+pub struct SubmitZkProof<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+    #[account(mut, has_one = provider @ TrustNetError::Unauthorized)]
+    pub job: Account<'info, JobEscrow>,
+    #[account(
+        seeds = [b"zk_vk", job.verification_data[..32].as_ref()],
+        bump
+    )]
+    pub verifying_key: Account<'info, VerifyingKey>,
+    /// CHECK: the provider's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.provider.as_ref()],
+        bump
+    )]
+    pub provider_reputation: UncheckedAccount<'info>,
+    /// CHECK: the client's reputation PDA, loaded with `load_reputation` whenever it exists
+    #[account(
+        mut,
+        seeds = [b"reputation", job.client.as_ref()],
+        bump
+    )]
+    pub client_reputation: UncheckedAccount<'info>,
+    /// Required when the job has a category.
+    #[account(
+        mut,
+        seeds = [b"category_rep", job.provider.as_ref(), &[job.category.unwrap_or_default()]],
+        bump
+    )]
+    pub provider_category_reputation: Option<Account<'info, CategoryReputation>>,
+    #[account(
+        mut,
+        seeds = [b"job_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_vault: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"job_token_vault", job.key().as_ref()],
+        bump
+    )]
+    pub job_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub provider_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Verifies the provider's Groth16 proof against the job's committed verifying key
+/// and releases the escrow on success. The first public input must be the job's
+/// `zk_job_binding`, so a proof cannot be replayed on another job.
+pub fn handler(ctx: Context<SubmitZkProof>, proof: Groth16Proof, public_inputs: Vec<[u8; 32]>) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    require!(job.status == JobStatus::Active, TrustNetError::InvalidStatus);
+    require!(job.verification_type == VerifyType::ZkVerify, TrustNetError::InvalidStatus);
+    require!(job.milestones.is_empty(), TrustNetError::InvalidMilestone);
+
+    let binding = zk_job_binding(&job.key(), &job.verification_data[32..]);
+    let first = public_inputs.first().ok_or(TrustNetError::InvalidProof)?;
+    require!(*first == binding, TrustNetError::InvalidProof);
+    require!(
+        groth16::verify(&ctx.accounts.verifying_key, &proof, &public_inputs)?,
+        TrustNetError::InvalidProof
+    );
+    job.submitted_at = Some(now_ts(&Clock::get()?));
+
+    let token = TokenVault::for_mint(
+        job.mint,
+        &ctx.accounts.job_token_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_program,
+    )?;
+    let escrow = Escrow::new(b"job_vault", job.key(), &ctx.accounts.job_vault, &ctx.accounts.system_program, token);
+    release_to_provider(
+        job,
+        &escrow,
+        &ctx.accounts.provider,
+        &ctx.accounts.provider_token_account,
+        &ctx.accounts.treasury,
+        &ctx.accounts.treasury_token_account,
+    )?;
+    settle_reputations(
+        job,
+        &ctx.accounts.provider_reputation,
+        &ctx.accounts.client_reputation,
+        ctx.accounts.provider_category_reputation.as_deref_mut(),
+        true,
+        job.amount,
+        now_ts(&Clock::get()?),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::state::reputation::AgentReputation;
+use crate::utils::constants::{MIN_REPUTATION_STAKE_LAMPORTS, REPUTATION_UNBONDING_SECONDS, now_ts};
+use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::transfer_from_vault;
+
+#[derive(Accounts)]
+pub struct UnstakeReputation<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    #[account(mut, has_one = agent)]
+    pub reputation: Account<'info, AgentReputation>,
+    #[account(
+        mut,
+        seeds = [b"rep_vault", agent.key().as_ref()],
+        bump
+    )]
+    pub rep_vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves `amount` of active stake into unbonding. The remaining stake must stay at or
+/// above the minimum; unbonding all of it closes the profile instead. A new request
+/// adds to any pending amount and restarts the delay. Not allowed while the agent has
+/// open jobs, whose outcome could still slash the stake.
+pub fn request_handler(ctx: Context<UnstakeReputation>, amount: u64) -> Result<()> {
+    let reputation = &mut ctx.accounts.reputation;
+    require!(reputation.open_jobs == 0, TrustNetError::OpenJobsPending);
+    require!(amount > 0 && amount <= reputation.stake_amount, TrustNetError::InsufficientFunds);
+    let remaining = reputation.stake_amount - amount;
+    require!(
+        remaining == 0 || remaining >= MIN_REPUTATION_STAKE_LAMPORTS,
+        TrustNetError::MinimumStakeNotMet
+    );
+
+    let now = now_ts(&Clock::get()?);
+    reputation.stake_amount = remaining;
+    reputation.unbonding_amount = reputation.unbonding_amount.saturating_add(amount);
+    reputation.unbonding_ready_at = now.saturating_add(REPUTATION_UNBONDING_SECONDS);
+    if remaining == 0 {
+        reputation.closed = true;
+    }
+    Ok(())
+}
+
+/// Pays out the unbonding stake once the delay has passed and no job is open. Returns the
+/// amount withdrawn.
+pub fn withdraw_handler(ctx: Context<UnstakeReputation>) -> Result<u64> {
+    let reputation = &mut ctx.accounts.reputation;
+    let amount = reputation.unbonding_amount;
+    require!(amount > 0, TrustNetError::InsufficientFunds);
+    require!(reputation.open_jobs == 0, TrustNetError::OpenJobsPending);
+    require!(now_ts(&Clock::get()?) >= reputation.unbonding_ready_at, TrustNetError::StakeUnbonding);
+    reputation.unbonding_amount = 0;
+
+    let agent = ctx.accounts.agent.key();
+    let seeds: &[&[u8]] = &[b"rep_vault", agent.as_ref()];
+    transfer_from_vault(
+        &ctx.accounts.rep_vault.to_account_info(),
+        &ctx.accounts.agent.to_account_info(),
+        &ctx.accounts.system_program,
+        seeds,
+        amount,
+    )?;
+    Ok(amount)
+}
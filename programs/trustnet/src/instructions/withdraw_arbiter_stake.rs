@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::state::arbiter::Arbiter;
+use crate::utils::constants::now_ts;
+use crate::utils::errors::TrustNetError;
+use crate::utils::escrow::transfer_from_vault;
+
+#[derive(Accounts)]
+pub struct WithdrawArbiterStake<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut, has_one = authority @ TrustNetError::Unauthorized)]
+    pub arbiter: Account<'info, Arbiter>,
+    #[account(
+        mut,
+        seeds = [b"arbiter_vault", authority.key().as_ref()],
+        bump
+    )]
+    pub arbiter_vault: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Pays out a deactivated arbiter's stake once the cooldown has passed and every
+/// dispute it voted on is resolved. Returns the amount withdrawn.
+pub fn handler(ctx: Context<WithdrawArbiterStake>) -> Result<u64> {
+    let arbiter = &mut ctx.accounts.arbiter;
+    require!(!arbiter.active, TrustNetError::ArbiterActive);
+    require!(arbiter.open_cases == 0, TrustNetError::ArbiterHasOpenCases);
+    require!(now_ts(&Clock::get()?) >= arbiter.withdrawable_at, TrustNetError::StakeUnbonding);
+    let amount = arbiter.stake;
+    require!(amount > 0, TrustNetError::InsufficientFunds);
+    arbiter.stake = 0;
+
+    let authority = ctx.accounts.authority.key();
+    let seeds: &[&[u8]] = &[b"arbiter_vault", authority.as_ref()];
+    transfer_from_vault(
+        &ctx.accounts.arbiter_vault.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program,
+        seeds,
+        amount,
+    )?;
+    Ok(amount)
+}
@@ -26,15 +26,91 @@ pub mod trustnet {
         Ok(())
     }
 
+    pub fn define_milestones(
+        ctx: Context<define_milestones::DefineMilestones>,
+        milestones: Vec<state::job_escrow::MilestoneSpec>,
+    ) -> Result<()> {
+        define_milestones::handler(ctx, milestones)?;
+        emit!(MilestonesDefined { job: ctx.accounts.job.key() });
+        Ok(())
+    }
+
+    pub fn set_job_requirements(
+        ctx: Context<set_job_requirements::SetJobRequirements>,
+        requirements: state::job_escrow::ProviderRequirements,
+    ) -> Result<()> {
+        set_job_requirements::handler(ctx, requirements)?;
+        emit!(JobRequirementsSet { job: ctx.accounts.job.key() });
+        Ok(())
+    }
+
+    pub fn set_job_category(ctx: Context<set_job_category::SetJobCategory>, category: u8) -> Result<()> {
+        set_job_category::handler(ctx, category)?;
+        emit!(JobCategorySet { job: ctx.accounts.job.key(), category });
+        Ok(())
+    }
+
+    pub fn open_bidding(ctx: Context<open_bidding::OpenBidding>, commit_deadline: i64, reveal_deadline: i64) -> Result<()> {
+        open_bidding::handler(ctx, commit_deadline, reveal_deadline)?;
+        emit!(BiddingOpened { job: ctx.accounts.job.key() });
+        Ok(())
+    }
+
+    pub fn commit_bid(ctx: Context<commit_bid::CommitBid>, bid_hash: [u8; 32]) -> Result<()> {
+        commit_bid::handler(ctx, bid_hash)?;
+        emit!(BidCommitted { job: ctx.accounts.job.key(), bidder: ctx.accounts.bidder.key() });
+        Ok(())
+    }
+
+    pub fn reveal_bid(ctx: Context<reveal_bid::RevealBid>, amount: u64, salt: Vec<u8>) -> Result<()> {
+        reveal_bid::handler(ctx, amount, salt)?;
+        emit!(BidRevealed { job: ctx.accounts.job.key(), bidder: ctx.accounts.bidder.key(), amount });
+        Ok(())
+    }
+
+    pub fn select_bid(ctx: Context<award_bid::AwardBid>) -> Result<()> {
+        award_bid::select_handler(ctx)?;
+        emit!(BidAwarded { job: ctx.accounts.job.key(), bidder: ctx.accounts.bid.bidder });
+        Ok(())
+    }
+
+    pub fn finalize_auction(ctx: Context<award_bid::AwardBid>) -> Result<()> {
+        award_bid::finalize_handler(ctx)?;
+        emit!(BidAwarded { job: ctx.accounts.job.key(), bidder: ctx.accounts.bid.bidder });
+        Ok(())
+    }
+
     pub fn accept_job(ctx: Context<accept_job::AcceptJob>, stake_amount: u64) -> Result<()> {
         accept_job::handler(ctx, stake_amount)?;
-        emit!(JobAccepted { job: ctx.accounts.job.key() });
+        emit!(JobAccepted { job: ctx.accounts.job.key(), provider: ctx.accounts.provider.key() });
         Ok(())
     }
 
     pub fn submit_completion(ctx: Context<submit_completion::SubmitCompletion>, submission_hash: [u8; 32]) -> Result<()> {
         submit_completion::handler(ctx, submission_hash)?;
-        emit!(JobSubmitted { job: ctx.accounts.job.key() });
+        let submission = &ctx.accounts.submission;
+        emit!(JobSubmitted {
+            job: ctx.accounts.job.key(),
+            submission: submission.key(),
+            index: submission.index,
+            submission_hash,
+            submitted_at: submission.submitted_at,
+        });
+        Ok(())
+    }
+
+    pub fn submit_milestone(
+        ctx: Context<submit_completion::SubmitCompletion>,
+        milestone_index: u8,
+        submission_hash: [u8; 32],
+    ) -> Result<()> {
+        submit_completion::milestone_handler(ctx, milestone_index, submission_hash)?;
+        emit!(MilestoneSubmitted {
+            job: ctx.accounts.job.key(),
+            index: milestone_index,
+            submission: ctx.accounts.submission.key(),
+            submission_hash,
+        });
         Ok(())
     }
 
@@ -44,17 +120,154 @@ pub mod trustnet {
         Ok(())
     }
 
+    pub fn reject_submission(ctx: Context<reject_submission::RejectSubmission>, feedback_hash: [u8; 32]) -> Result<()> {
+        reject_submission::handler(ctx, feedback_hash)?;
+        let job = &ctx.accounts.job;
+        emit!(SubmissionRejected { job: job.key(), feedback_hash, revision: job.revision_count });
+        if job.status == state::job_escrow::JobStatus::Disputed {
+            if let Some(dispute) = &ctx.accounts.dispute {
+                emit!(JobDisputed { job: job.key(), dispute: dispute.key(), submission_hash: dispute.submission_hash });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn approve_milestone(ctx: Context<approve_completion::ApproveCompletion>, milestone_index: u8) -> Result<()> {
+        approve_completion::milestone_handler(ctx, milestone_index)?;
+        emit!(MilestoneReleased { job: ctx.accounts.job.key(), index: milestone_index });
+        Ok(())
+    }
+
     pub fn oracle_verify(ctx: Context<oracle_verify::OracleVerify>, approved: bool, notes_hash: [u8; 32]) -> Result<()> {
         oracle_verify::handler(ctx, approved, notes_hash)?;
+        let job = &ctx.accounts.job;
         if approved {
-            emit!(JobCompleted { job: ctx.accounts.job.key() });
+            emit!(JobCompleted { job: job.key() });
+        } else {
+            emit!(JobRejected { job: job.key(), notes_hash, action: job.oracle_reject_action() });
+            if let Some(dispute) = &ctx.accounts.dispute {
+                emit!(JobDisputed { job: job.key(), dispute: dispute.key(), submission_hash: dispute.submission_hash });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_oracle_committee(
+        ctx: Context<set_oracle_committee::SetOracleCommittee>,
+        oracles: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        set_oracle_committee::handler(ctx, oracles, threshold)?;
+        emit!(OracleCommitteeSet { job: ctx.accounts.job.key(), committee: ctx.accounts.committee.key() });
+        Ok(())
+    }
+
+    pub fn attest_oracle(ctx: Context<attest_oracle::AttestOracle>, approved: bool, notes_hash: [u8; 32]) -> Result<()> {
+        attest_oracle::handler(ctx, approved, notes_hash)?;
+        let job = &ctx.accounts.job;
+        emit!(OracleAttested { job: job.key(), oracle: ctx.accounts.oracle.key(), approved });
+        match job.status {
+            state::job_escrow::JobStatus::Completed => emit!(JobCompleted { job: job.key() }),
+            state::job_escrow::JobStatus::Submitted => {}
+            _ => {
+                emit!(JobRejected { job: job.key(), notes_hash, action: job.oracle_reject_action() });
+                if let Some(dispute) = &ctx.accounts.dispute {
+                    emit!(JobDisputed { job: job.key(), dispute: dispute.key(), submission_hash: dispute.submission_hash });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_review_panel(
+        ctx: Context<set_review_panel::SetReviewPanel>,
+        reviewers: Vec<Pubkey>,
+        seats: u8,
+        specialization: Option<u8>,
+        bond: u64,
+    ) -> Result<()> {
+        set_review_panel::handler(ctx, reviewers, seats, specialization, bond)?;
+        emit!(ReviewPanelSet { job: ctx.accounts.job.key(), panel: ctx.accounts.panel.key() });
+        Ok(())
+    }
+
+    pub fn submit_review(ctx: Context<submit_review::SubmitReview>, score: u8, comment_hash: [u8; 32]) -> Result<()> {
+        submit_review::handler(ctx, score, comment_hash)?;
+        let job = &ctx.accounts.job;
+        emit!(ReviewSubmitted { job: job.key(), reviewer: ctx.accounts.reviewer.key(), score });
+        if job.status != state::job_escrow::JobStatus::Submitted {
+            if job.status == state::job_escrow::JobStatus::Completed {
+                emit!(JobCompleted { job: job.key() });
+            }
+            if !ctx.accounts.provider_reputation.data_is_empty() {
+                emit!(ReputationUpdated { agent: job.provider });
+            }
         }
         Ok(())
     }
 
+    pub fn claim_review_reward(ctx: Context<claim_review_reward::ClaimReviewReward>) -> Result<()> {
+        claim_review_reward::handler(ctx)
+    }
+
+    pub fn register_verifying_key(
+        ctx: Context<register_verifying_key::RegisterVerifyingKey>,
+        alpha_g1: [u8; 64],
+        beta_g2: [u8; 128],
+        gamma_g2: [u8; 128],
+        delta_g2: [u8; 128],
+        ic: Vec<[u8; 64]>,
+    ) -> Result<()> {
+        register_verifying_key::handler(ctx, alpha_g1, beta_g2, gamma_g2, delta_g2, ic)?;
+        emit!(VerifyingKeyRegistered {
+            verifying_key: ctx.accounts.verifying_key.key(),
+            hash: ctx.accounts.verifying_key.hash,
+        });
+        Ok(())
+    }
+
+    pub fn submit_zk_proof(
+        ctx: Context<submit_zk_proof::SubmitZkProof>,
+        proof: utils::groth16::Groth16Proof,
+        public_inputs: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        submit_zk_proof::handler(ctx, proof, public_inputs)?;
+        emit!(JobCompleted { job: ctx.accounts.job.key() });
+        Ok(())
+    }
+
     pub fn raise_dispute(ctx: Context<raise_dispute::RaiseDispute>, reason_bytes: Vec<u8>, evidence_hash: [u8; 32]) -> Result<()> {
         raise_dispute::handler(ctx, reason_bytes, evidence_hash)?;
-        emit!(JobDisputed { job: ctx.accounts.job.key(), dispute: ctx.accounts.dispute.key() });
+        let dispute = &ctx.accounts.dispute;
+        emit!(JobDisputed { job: ctx.accounts.job.key(), dispute: dispute.key(), submission_hash: dispute.submission_hash });
+        Ok(())
+    }
+
+    pub fn raise_milestone_dispute(
+        ctx: Context<raise_dispute::RaiseMilestoneDispute>,
+        milestone_index: u8,
+        reason_bytes: Vec<u8>,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        raise_dispute::milestone_handler(ctx, milestone_index, reason_bytes, evidence_hash)?;
+        let dispute = &ctx.accounts.dispute;
+        emit!(JobDisputed { job: ctx.accounts.job.key(), dispute: dispute.key(), submission_hash: dispute.submission_hash });
+        Ok(())
+    }
+
+    pub fn select_arbiters<'info>(
+        ctx: Context<'_, '_, 'info, 'info, select_arbiters::SelectArbiters<'info>>,
+    ) -> Result<()> {
+        select_arbiters::handler(ctx)?;
+        let dispute = &ctx.accounts.dispute;
+        // A draw whose seed slot aged out only re-arms it.
+        if dispute.status == state::dispute::DisputeStatus::CommitPhase {
+            emit!(ArbitersSelected {
+                dispute: dispute.key(),
+                arbiters: dispute.selected_arbiters.clone(),
+                selection_seed: dispute.selection_seed,
+            });
+        }
         Ok(())
     }
 
@@ -70,15 +283,37 @@ pub mod trustnet {
         Ok(())
     }
 
-    pub fn resolve_dispute(ctx: Context<resolve_dispute::ResolveDispute>) -> Result<()> {
-        resolve_dispute::handler(ctx)?;
-        emit!(DisputeResolved { dispute: ctx.accounts.dispute.key() });
+    pub fn resolve_dispute<'info>(ctx: Context<'_, '_, 'info, 'info, resolve_dispute::ResolveDispute<'info>>) -> Result<()> {
+        let (slashed, scores) = resolve_dispute::handler(ctx)?;
+        let dispute = &ctx.accounts.dispute;
+        emit!(DisputeResolved { dispute: dispute.key(), submission_hash: dispute.submission_hash });
+        if slashed > 0 {
+            emit!(ProviderSlashed { job: ctx.accounts.job.key(), provider: ctx.accounts.job.provider, amount: slashed });
+        }
+        for score in scores {
+            emit!(ArbiterScored {
+                dispute: dispute.key(),
+                arbiter: score.arbiter,
+                correct: score.correct,
+                accuracy_score: score.accuracy_score,
+                cases_judged: score.cases_judged,
+            });
+        }
         Ok(())
     }
 
     pub fn expire_job(ctx: Context<expire_job::ExpireJob>) -> Result<()> {
-        expire_job::handler(ctx)?;
+        let slashed = expire_job::handler(ctx)?;
         emit!(JobExpired { job: ctx.accounts.job.key() });
+        if slashed > 0 {
+            emit!(ProviderSlashed { job: ctx.accounts.job.key(), provider: ctx.accounts.job.provider, amount: slashed });
+        }
+        Ok(())
+    }
+
+    pub fn cancel_job(ctx: Context<cancel_job::CancelJob>) -> Result<()> {
+        cancel_job::handler(ctx)?;
+        emit!(JobCancelled { job: ctx.accounts.job.key() });
         Ok(())
     }
 
@@ -94,6 +329,44 @@ pub mod trustnet {
         Ok(())
     }
 
+    pub fn request_unstake(ctx: Context<unstake_reputation::UnstakeReputation>, amount: u64) -> Result<()> {
+        unstake_reputation::request_handler(ctx, amount)?;
+        emit!(UnstakeRequested {
+            agent: ctx.accounts.agent.key(),
+            amount,
+            ready_at: ctx.accounts.reputation.unbonding_ready_at,
+            closed: ctx.accounts.reputation.closed,
+        });
+        Ok(())
+    }
+
+    pub fn withdraw_stake(ctx: Context<unstake_reputation::UnstakeReputation>) -> Result<()> {
+        let amount = unstake_reputation::withdraw_handler(ctx)?;
+        emit!(StakeWithdrawn { agent: ctx.accounts.agent.key(), amount });
+        Ok(())
+    }
+
+    pub fn get_reputation_score(
+        ctx: Context<get_reputation_score::GetReputationScore>,
+    ) -> Result<state::reputation::ReputationScore> {
+        get_reputation_score::handler(ctx)
+    }
+
+    pub fn init_category_reputation(
+        ctx: Context<init_category_reputation::InitCategoryReputation>,
+        category: u8,
+    ) -> Result<()> {
+        init_category_reputation::handler(ctx, category)?;
+        emit!(ReputationUpdated { agent: ctx.accounts.agent.key() });
+        Ok(())
+    }
+
+    pub fn get_category_score(
+        ctx: Context<get_reputation_score::GetCategoryScore>,
+    ) -> Result<state::reputation::ReputationScore> {
+        get_reputation_score::category_handler(ctx)
+    }
+
     pub fn rate_job(
         ctx: Context<rate_job::RateJob>,
         job_id: [u8; 32],
@@ -106,11 +379,40 @@ pub mod trustnet {
         Ok(())
     }
 
+    pub fn init_arbiter_registry(ctx: Context<init_arbiter_registry::InitArbiterRegistry>) -> Result<()> {
+        init_arbiter_registry::handler(ctx)
+    }
+
+    pub fn add_arbiter_registry_page(ctx: Context<add_arbiter_registry_page::AddArbiterRegistryPage>) -> Result<()> {
+        add_arbiter_registry_page::handler(ctx)
+    }
+
     pub fn register_arbiter(ctx: Context<register_arbiter::RegisterArbiter>, stake_amount: u64, specializations: Vec<u8>) -> Result<()> {
         register_arbiter::handler(ctx, stake_amount, specializations)?;
         emit!(ArbiterRegistered { arbiter: ctx.accounts.arbiter.key() });
         Ok(())
     }
+
+    pub fn add_arbiter_stake(ctx: Context<add_arbiter_stake::AddArbiterStake>, amount: u64) -> Result<()> {
+        add_arbiter_stake::handler(ctx, amount)?;
+        emit!(ArbiterStakeAdded { arbiter: ctx.accounts.arbiter.key(), amount, stake: ctx.accounts.arbiter.stake });
+        Ok(())
+    }
+
+    pub fn deactivate_arbiter(ctx: Context<deactivate_arbiter::DeactivateArbiter>) -> Result<()> {
+        deactivate_arbiter::handler(ctx)?;
+        emit!(ArbiterDeactivated {
+            arbiter: ctx.accounts.arbiter.key(),
+            withdrawable_at: ctx.accounts.arbiter.withdrawable_at,
+        });
+        Ok(())
+    }
+
+    pub fn withdraw_arbiter_stake(ctx: Context<withdraw_arbiter_stake::WithdrawArbiterStake>) -> Result<()> {
+        let amount = withdraw_arbiter_stake::handler(ctx)?;
+        emit!(ArbiterStakeWithdrawn { arbiter: ctx.accounts.arbiter.key(), amount });
+        Ok(())
+    }
 }
 
 #[event]
@@ -118,14 +420,73 @@ pub struct JobCreated {
     pub job: Pubkey,
 }
 
+#[event]
+pub struct MilestonesDefined {
+    pub job: Pubkey,
+}
+
+#[event]
+pub struct JobRequirementsSet {
+    pub job: Pubkey,
+}
+
+#[event]
+pub struct JobCategorySet {
+    pub job: Pubkey,
+    pub category: u8,
+}
+
+#[event]
+pub struct BiddingOpened {
+    pub job: Pubkey,
+}
+
+#[event]
+pub struct BidCommitted {
+    pub job: Pubkey,
+    pub bidder: Pubkey,
+}
+
+#[event]
+pub struct BidRevealed {
+    pub job: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct BidAwarded {
+    pub job: Pubkey,
+    pub bidder: Pubkey,
+}
+
 #[event]
 pub struct JobAccepted {
     pub job: Pubkey,
+    pub provider: Pubkey,
 }
 
 #[event]
 pub struct JobSubmitted {
     pub job: Pubkey,
+    pub submission: Pubkey,
+    pub index: u16,
+    pub submission_hash: [u8; 32],
+    pub submitted_at: i64,
+}
+
+#[event]
+pub struct MilestoneSubmitted {
+    pub job: Pubkey,
+    pub index: u8,
+    pub submission: Pubkey,
+    pub submission_hash: [u8; 32],
+}
+
+#[event]
+pub struct MilestoneReleased {
+    pub job: Pubkey,
+    pub index: u8,
 }
 
 #[event]
@@ -133,10 +494,64 @@ pub struct JobCompleted {
     pub job: Pubkey,
 }
 
+#[event]
+pub struct SubmissionRejected {
+    pub job: Pubkey,
+    pub feedback_hash: [u8; 32],
+    pub revision: u8,
+}
+
+#[event]
+pub struct OracleCommitteeSet {
+    pub job: Pubkey,
+    pub committee: Pubkey,
+}
+
+#[event]
+pub struct OracleAttested {
+    pub job: Pubkey,
+    pub oracle: Pubkey,
+    pub approved: bool,
+}
+
+#[event]
+pub struct ReviewPanelSet {
+    pub job: Pubkey,
+    pub panel: Pubkey,
+}
+
+#[event]
+pub struct ReviewSubmitted {
+    pub job: Pubkey,
+    pub reviewer: Pubkey,
+    pub score: u8,
+}
+
+#[event]
+pub struct VerifyingKeyRegistered {
+    pub verifying_key: Pubkey,
+    pub hash: [u8; 32],
+}
+
+#[event]
+pub struct JobRejected {
+    pub job: Pubkey,
+    pub notes_hash: [u8; 32],
+    pub action: state::job_escrow::OracleRejectAction,
+}
+
 #[event]
 pub struct JobDisputed {
     pub job: Pubkey,
     pub dispute: Pubkey,
+    pub submission_hash: [u8; 32],
+}
+
+#[event]
+pub struct ArbitersSelected {
+    pub dispute: Pubkey,
+    pub arbiters: Vec<Pubkey>,
+    pub selection_seed: [u8; 32],
 }
 
 #[event]
@@ -154,6 +569,23 @@ pub struct DisputeRevealed {
 #[event]
 pub struct DisputeResolved {
     pub dispute: Pubkey,
+    pub submission_hash: [u8; 32],
+}
+
+#[event]
+pub struct ArbiterScored {
+    pub dispute: Pubkey,
+    pub arbiter: Pubkey,
+    pub correct: bool,
+    pub accuracy_score: u16,
+    pub cases_judged: u64,
+}
+
+#[event]
+pub struct ProviderSlashed {
+    pub job: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
 }
 
 #[event]
@@ -161,12 +593,50 @@ pub struct JobExpired {
     pub job: Pubkey,
 }
 
+#[event]
+pub struct JobCancelled {
+    pub job: Pubkey,
+}
+
 #[event]
 pub struct ReputationUpdated {
     pub agent: Pubkey,
 }
 
+#[event]
+pub struct UnstakeRequested {
+    pub agent: Pubkey,
+    pub amount: u64,
+    pub ready_at: i64,
+    pub closed: bool,
+}
+
+#[event]
+pub struct StakeWithdrawn {
+    pub agent: Pubkey,
+    pub amount: u64,
+}
+
 #[event]
 pub struct ArbiterRegistered {
     pub arbiter: Pubkey,
 }
+
+#[event]
+pub struct ArbiterStakeAdded {
+    pub arbiter: Pubkey,
+    pub amount: u64,
+    pub stake: u64,
+}
+
+#[event]
+pub struct ArbiterDeactivated {
+    pub arbiter: Pubkey,
+    pub withdrawable_at: i64,
+}
+
+#[event]
+pub struct ArbiterStakeWithdrawn {
+    pub arbiter: Pubkey,
+    pub amount: u64,
+}
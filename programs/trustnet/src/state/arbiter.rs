@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::utils::constants::ARBITER_REGISTRY_PAGE_SIZE;
+use crate::utils::errors::TrustNetError;
+use crate::utils::math::{arbiter_accuracy_ema, arbiter_vote_weight};
+
 #[account]
 pub struct Arbiter {
     pub authority: Pubkey,
@@ -10,9 +14,110 @@ pub struct Arbiter {
     pub active: bool,
     pub created_at: i64,
     pub last_case: i64,
+    /// Registry page holding this arbiter's entry.
+    pub registry_page: u32,
+    /// Disputes the arbiter has committed a vote on that are not yet resolved.
+    pub open_cases: u16,
+    /// Unix time after which a deactivated arbiter can withdraw its stake.
+    pub withdrawable_at: i64,
     pub bump: u8,
 }
 
 impl Arbiter {
-    pub const LEN: usize = 8 + 32 + 8 + 8 + 2 + 4 + 64 + 1 + 8 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 2 + 4 + 64 + 1 + 8 + 8 + 4 + 2 + 8 + 1;
+
+    pub fn vote_weight(&self) -> u64 {
+        arbiter_vote_weight(self.stake, self.accuracy_score)
+    }
+
+    /// Scores a judged case; `correct` means the arbiter revealed a vote with the majority.
+    pub fn record_case(&mut self, correct: bool, now: i64) {
+        self.cases_judged = self.cases_judged.saturating_add(1);
+        self.accuracy_score = arbiter_accuracy_ema(self.accuracy_score, correct);
+        self.last_case = now;
+    }
+}
+
+/// Header of the paginated arbiter registry dispute panels are drawn from.
+#[account]
+pub struct ArbiterRegistry {
+    pub page_count: u32,
+    pub arbiter_count: u32,
+    pub total_stake: u64,
+    pub bump: u8,
+}
+
+impl ArbiterRegistry {
+    pub const LEN: usize = 8 + 4 + 4 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct RegistryEntry {
+    pub arbiter: Pubkey,
+    pub stake: u64,
+    /// Bitmap of the arbiter's specialization bytes.
+    pub specializations: [u8; 32],
+}
+
+impl RegistryEntry {
+    pub const LEN: usize = 32 + 8 + 32;
+
+    pub fn new(arbiter: &Account<Arbiter>) -> Self {
+        let mut specializations = [0u8; 32];
+        for s in arbiter.specializations.iter() {
+            specializations[(*s / 8) as usize] |= 1 << (*s % 8);
+        }
+        Self { arbiter: arbiter.key(), stake: arbiter.stake, specializations }
+    }
+
+    pub fn specializes_in(&self, category: u8) -> bool {
+        self.specializations[(category / 8) as usize] & (1 << (category % 8)) != 0
+    }
+}
+
+/// A fixed-size page of active arbiters, `[b"arbiter_registry_page", index]`.
+#[account]
+pub struct ArbiterRegistryPage {
+    pub index: u32,
+    pub entries: Vec<RegistryEntry>,
+    pub total_stake: u64,
+    pub bump: u8,
+}
+
+impl ArbiterRegistryPage {
+    pub const LEN: usize = 8 + 4 + 4 + RegistryEntry::LEN * ARBITER_REGISTRY_PAGE_SIZE + 8 + 1;
+
+    pub fn insert(&mut self, registry: &mut ArbiterRegistry, entry: RegistryEntry) -> Result<()> {
+        require!(self.entries.len() < ARBITER_REGISTRY_PAGE_SIZE, TrustNetError::ArbiterRegistryPageFull);
+        self.total_stake = self.total_stake.saturating_add(entry.stake);
+        registry.total_stake = registry.total_stake.saturating_add(entry.stake);
+        registry.arbiter_count = registry.arbiter_count.saturating_add(1);
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, registry: &mut ArbiterRegistry, arbiter: &Pubkey) -> Result<()> {
+        let index = self
+            .entries
+            .iter()
+            .position(|e| e.arbiter == *arbiter)
+            .ok_or(TrustNetError::InvalidArbiterSet)?;
+        let entry = self.entries.swap_remove(index);
+        self.total_stake = self.total_stake.saturating_sub(entry.stake);
+        registry.total_stake = registry.total_stake.saturating_sub(entry.stake);
+        registry.arbiter_count = registry.arbiter_count.saturating_sub(1);
+        Ok(())
+    }
+
+    pub fn set_stake(&mut self, registry: &mut ArbiterRegistry, arbiter: &Pubkey, stake: u64) -> Result<()> {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|e| e.arbiter == *arbiter)
+            .ok_or(TrustNetError::InvalidArbiterSet)?;
+        self.total_stake = self.total_stake.saturating_sub(entry.stake).saturating_add(stake);
+        registry.total_stake = registry.total_stake.saturating_sub(entry.stake).saturating_add(stake);
+        entry.stake = stake;
+        Ok(())
+    }
 }
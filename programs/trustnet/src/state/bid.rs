@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Bid {
+    pub job: Pubkey,
+    pub bidder: Pubkey,
+    pub commit_hash: [u8; 32],
+    pub revealed: bool,
+    pub amount: Option<u64>,
+    pub committed_at: i64,
+    pub bump: u8,
+}
+
+impl Bid {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 1 + 9 + 8 + 1;
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::utils::constants::ARBITER_PANEL_SIZE;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum DisputeStatus {
     Open,
@@ -21,12 +23,32 @@ pub struct Dispute {
     pub selected_arbiters: Vec<Pubkey>,
     pub client_weight: u64,
     pub provider_weight: u64,
+    pub commit_count: u8,
+    pub reveal_count: u8,
     pub resolved_in_favor_of_client: Option<bool>,
+    pub milestone: Option<u8>,
+    pub submission_index: u16,
+    pub submission_hash: [u8; 32],
+    /// Randomness the panel was drawn with: the hash of `selection_slot` mixed with the dispute key.
+    pub selection_seed: [u8; 32],
+    /// Future slot, fixed when the dispute opens, whose hash seeds the draw; nobody can time it.
+    pub selection_slot: u64,
     pub bump: u8,
 }
 
 impl Dispute {
-    pub const LEN: usize = 8 + 32 * 3 + 32 + 32 + 1 + 8 + 8 + 4 + 32 * 3 + 8 + 8 + 1 + 1;
+    pub const LEN: usize = 8 + 32 * 3 + 32 + 32 + 1 + 8 + 8 + 4 + 32 * ARBITER_PANEL_SIZE + 8 + 8 + 1 + 1 + 2 + 2 + 2 + 32 + 32 + 8 + 1;
+
+    /// Reveals open once the commit deadline has passed.
+    pub fn reveals_open(&self, now: i64) -> bool {
+        now > self.commit_deadline
+    }
+
+    /// Resolution waits for the reveal deadline unless the whole panel has revealed.
+    pub fn voting_closed(&self, now: i64) -> bool {
+        now > self.reveal_deadline
+            || (!self.selected_arbiters.is_empty() && self.reveal_count as usize == self.selected_arbiters.len())
+    }
 }
 
 #[account]
@@ -36,9 +58,11 @@ pub struct VoteCommitment {
     pub commit_hash: [u8; 32],
     pub revealed: bool,
     pub vote: Option<bool>,
+    /// The arbiter's vote weight when it revealed; rewards are paid on this, not on a later stake.
+    pub weight: u64,
     pub bump: u8,
 }
 
 impl VoteCommitment {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 1 + 1 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 1 + 2 + 8 + 1;
 }
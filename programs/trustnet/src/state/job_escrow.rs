@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::state::reputation::AgentReputation;
+use crate::utils::constants::{MAX_MILESTONES, MAX_REQUIRED_SPECIALIZATIONS, REVISION_WINDOW_SECONDS};
+use crate::utils::errors::TrustNetError;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum JobStatus {
     Created,
@@ -10,6 +14,7 @@ pub enum JobStatus {
     Resolved,
     Cancelled,
     Expired,
+    Failed,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -19,6 +24,91 @@ pub enum VerifyType {
     DeadlineAuto,
     PeerReview,
     ZkVerify,
+    OracleThreshold,
+}
+
+/// What an `oracle_verify(approved = false)` does, set in `verification_data[32]`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OracleRejectAction {
+    /// Job goes back to `Active` for rework.
+    Rework,
+    /// Job fails and the escrow plus provider stake go to the client.
+    Refund,
+    /// A dispute opens automatically.
+    Dispute,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum MilestoneStatus {
+    Pending,
+    Submitted,
+    Disputed,
+    Released,
+    Refunded,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MilestoneSpec {
+    pub amount: u64,
+    pub deadline: i64,
+    pub terms_hash: [u8; 32],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Milestone {
+    pub amount: u64,
+    pub deadline: i64,
+    pub terms_hash: [u8; 32],
+    pub status: MilestoneStatus,
+    pub submitted_at: Option<i64>,
+    pub submission_index: Option<u16>,
+    pub submission_hash: Option<[u8; 32]>,
+}
+
+impl Milestone {
+    pub const LEN: usize = 8 + 8 + 32 + 1 + 9 + 3 + 33;
+
+    pub fn is_settled(&self) -> bool {
+        self.status == MilestoneStatus::Released || self.status == MilestoneStatus::Refunded
+    }
+}
+
+/// Eligibility rules a provider must meet to accept a job.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ProviderRequirements {
+    pub min_reputation_score: u8,
+    pub min_stake: u64,
+    pub specializations: Vec<u8>,
+}
+
+impl ProviderRequirements {
+    pub const LEN: usize = 1 + 8 + 4 + MAX_REQUIRED_SPECIALIZATIONS;
+
+    pub fn is_empty(&self) -> bool {
+        self.min_reputation_score == 0 && self.min_stake == 0 && self.specializations.is_empty()
+    }
+
+    pub fn is_met_by(&self, reputation: &AgentReputation, now: i64) -> bool {
+        !reputation.closed
+            && reputation.score(now).score >= self.min_reputation_score
+            && reputation.stake_amount >= self.min_stake
+            && self.specializations.iter().all(|s| reputation.specializations.contains(s))
+    }
+}
+
+/// Sealed-bid reverse auction run on an open job before it is accepted.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Auction {
+    pub commit_deadline: i64,
+    pub reveal_deadline: i64,
+    pub bid_count: u32,
+    pub lowest_bidder: Pubkey,
+    pub lowest_amount: u64,
+    pub awarded: bool,
+}
+
+impl Auction {
+    pub const LEN: usize = 8 + 8 + 4 + 32 + 8 + 1;
 }
 
 #[account]
@@ -36,9 +126,117 @@ pub struct JobEscrow {
     pub submitted_at: Option<i64>,
     pub completed_at: Option<i64>,
     pub terms_hash: [u8; 32],
+    pub mint: Option<Pubkey>,
+    pub milestones: Vec<Milestone>,
+    pub requirements: ProviderRequirements,
+    pub auction: Option<Auction>,
+    pub revision_count: u8,
+    pub feedback_hash: Option<[u8; 32]>,
+    pub oracle_notes_hash: Option<[u8; 32]>,
+    pub submission_count: u16,
+    pub submission_hash: Option<[u8; 32]>,
+    /// Set by `set_job_category`; settlement then also updates the provider's `CategoryReputation`.
+    pub category: Option<u8>,
+    /// Set when the provider had a reputation profile at accept, which then counts the job in
+    /// `open_jobs` until settlement.
+    pub provider_has_reputation: bool,
     pub bump: u8,
 }
 
 impl JobEscrow {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1 + 1 + 64 + 8 + 9 + 9 + 32 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1 + 1 + 64 + 8 + 9 + 9 + 32 + 33
+        + 4 + Milestone::LEN * MAX_MILESTONES
+        + ProviderRequirements::LEN
+        + 1 + Auction::LEN
+        + 1 + 33
+        + 33
+        + 2 + 33
+        + 2
+        + 1
+        + 1;
+
+    /// Escrowed job payment not yet released or refunded.
+    pub fn outstanding_amount(&self) -> u64 {
+        if self.milestones.is_empty() {
+            return self.amount;
+        }
+        self.milestones
+            .iter()
+            .filter(|m| !m.is_settled())
+            .map(|m| m.amount)
+            .sum()
+    }
+
+    /// Payment that ended up with the provider; milestone jobs count only released milestones.
+    pub fn released_amount(&self) -> u64 {
+        if self.milestones.is_empty() {
+            return self.amount;
+        }
+        self.milestones
+            .iter()
+            .filter(|m| m.status == MilestoneStatus::Released)
+            .map(|m| m.amount)
+            .sum()
+    }
+
+    /// `amount` of this job in lamports, for volume and rating weights. Mint-denominated
+    /// amounts are in the mint's base units and have no lamport value, so they count as 0.
+    pub fn lamport_value(&self, amount: u64) -> u64 {
+        if self.mint.is_some() {
+            0
+        } else {
+            amount
+        }
+    }
+
+    /// Whether a completed job was delivered in full, i.e. no milestone was refunded.
+    pub fn fully_delivered(&self) -> bool {
+        self.status == JobStatus::Completed
+            && self.milestones.iter().all(|m| m.status != MilestoneStatus::Refunded)
+    }
+
+    /// Revision rounds a ClientApproval client may request, taken from `verification_data[0]`.
+    pub fn max_revisions(&self) -> u8 {
+        self.verification_data[0]
+    }
+
+    /// Sends a rejected submission back for rework. The deadline moves to at least
+    /// `REVISION_WINDOW_SECONDS` from now, so a rejection cannot leave the job already expired.
+    /// `submitted_at` keeps the time of the rejected submission.
+    pub fn reopen_for_revision(&mut self, now: i64) {
+        self.status = JobStatus::Active;
+        self.deadline = self.deadline.max(now).saturating_add(REVISION_WINDOW_SECONDS);
+    }
+
+    pub fn oracle_reject_action(&self) -> OracleRejectAction {
+        match self.verification_data[32] {
+            1 => OracleRejectAction::Refund,
+            2 => OracleRejectAction::Dispute,
+            _ => OracleRejectAction::Rework,
+        }
+    }
+
+    /// Open jobs are created without a provider; the first eligible one to accept is assigned.
+    pub fn is_open(&self) -> bool {
+        self.provider == Pubkey::default()
+    }
+
+    /// Checks the job's provider requirements against the candidate's reputation.
+    pub fn check_provider_eligible(&self, reputation: Option<&AgentReputation>, now: i64) -> Result<()> {
+        if self.requirements.is_empty() {
+            return Ok(());
+        }
+        let reputation = reputation.ok_or(TrustNetError::ProviderNotEligible)?;
+        require!(self.requirements.is_met_by(reputation, now), TrustNetError::ProviderNotEligible);
+        Ok(())
+    }
+
+    /// Index of the latest whole-job submission; disputes reference it.
+    pub fn latest_submission(&self) -> u16 {
+        self.submission_count.saturating_sub(1)
+    }
+
+    pub fn milestones_settled(&self) -> bool {
+        !self.milestones.is_empty() && self.milestones.iter().all(Milestone::is_settled)
+    }
 }
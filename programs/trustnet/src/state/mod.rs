@@ -2,3 +2,8 @@ pub mod job_escrow;
 pub mod reputation;
 pub mod arbiter;
 pub mod dispute;
+pub mod bid;
+pub mod oracle;
+pub mod review;
+pub mod zk;
+pub mod submission;
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::utils::constants::MAX_ORACLES;
+
+/// M-of-N oracle set for an `OracleThreshold` job.
+#[account]
+pub struct OracleCommittee {
+    pub job: Pubkey,
+    pub oracles: Vec<Pubkey>,
+    pub threshold: u8,
+    pub approvals: u8,
+    pub rejections: u8,
+    /// Bumped each time a rejection sends the job back for rework, so oracles attest afresh.
+    pub round: u16,
+    pub bump: u8,
+}
+
+impl OracleCommittee {
+    pub const LEN: usize = 8 + 32 + 4 + 32 * MAX_ORACLES + 1 + 1 + 1 + 2 + 1;
+
+    pub fn approved(&self) -> bool {
+        self.approvals >= self.threshold
+    }
+
+    /// True once too many oracles have rejected for the threshold to still be met.
+    pub fn rejected(&self) -> bool {
+        self.rejections as usize > self.oracles.len() - self.threshold as usize
+    }
+}
+
+#[account]
+pub struct OracleAttestation {
+    pub job: Pubkey,
+    pub oracle: Pubkey,
+    pub round: u16,
+    pub approved: bool,
+    pub notes_hash: [u8; 32],
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl OracleAttestation {
+    pub const LEN: usize = 8 + 32 + 32 + 2 + 1 + 32 + 8 + 1;
+}
@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::state::job_escrow::JobEscrow;
+use crate::utils::constants::{BPS_DENOMINATOR, MIN_REPUTATION_STAKE_LAMPORTS};
+use crate::utils::errors::TrustNetError;
+use crate::utils::math::{bayesian_rating, compute_rating_average, reputation_score_breakdown};
+
 #[account]
 pub struct AgentReputation {
     pub agent: Pubkey,
@@ -7,18 +12,246 @@ pub struct AgentReputation {
     pub total_jobs_failed: u64,
     pub total_disputes_won: u64,
     pub total_disputes_lost: u64,
+    /// Lamports of delivered jobs; mint-denominated jobs add nothing.
     pub total_volume: u64,
     pub avg_rating: u16,
+    /// Confidence-adjusted average, scaled like `avg_rating`; see `utils::math::bayesian_rating`.
+    pub bayesian_rating: u16,
     pub rating_count: u64,
+    /// Sum of `score * 100` over all ratings.
+    pub rating_sum: u64,
     pub specializations: Vec<u8>,
     pub created_at: i64,
     pub last_active: i64,
     pub stake_amount: u64,
+    /// Sum of `weight * score * 100` over all ratings; see `utils::math::rating_weight`.
+    pub weighted_rating_sum: u128,
+    pub rating_weight_total: u128,
+    /// Average rating weighted by job value and rater score, scaled like `avg_rating`.
+    pub weighted_avg_rating: u16,
+    /// Stake requested for withdrawal. It no longer counts toward the score but stays
+    /// in `rep_vault`, and slashable, until `withdraw_stake` after `unbonding_ready_at`.
+    pub unbonding_amount: u64,
+    pub unbonding_ready_at: i64,
+    /// Set when the whole stake is unbonded; staking back above the minimum reopens it.
+    pub closed: bool,
+    /// Jobs accepted with this profile and not yet settled. Stake cannot unbond while any are open.
+    pub open_jobs: u32,
     pub bump: u8,
 }
 
 impl AgentReputation {
-    pub const LEN: usize = 8 + 32 + 8 * 5 + 2 + 8 + 4 + 64 + 8 + 8 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 8 * 5 + 2 + 2 + 8 + 8 + 4 + 64 + 8 + 8 + 8 + 16 + 16 + 2 + 8 + 8 + 1 + 4 + 1;
+
+    pub fn record_rating(&mut self, score: u8, weight: u128, now: i64) {
+        self.avg_rating = compute_rating_average(self.avg_rating, self.rating_count, score);
+        self.rating_count = self.rating_count.saturating_add(1);
+        self.rating_sum = self.rating_sum.saturating_add(score as u64 * 100);
+        self.bayesian_rating = bayesian_rating(self.rating_sum, self.rating_count);
+        self.weighted_rating_sum = self.weighted_rating_sum.saturating_add(weight.saturating_mul(score as u128 * 100));
+        self.rating_weight_total = self.rating_weight_total.saturating_add(weight);
+        if self.rating_weight_total > 0 {
+            self.weighted_avg_rating = (self.weighted_rating_sum / self.rating_weight_total) as u16;
+        }
+        self.last_active = now;
+    }
+
+    /// Open and staked at least `MIN_REPUTATION_STAKE_LAMPORTS`, not counting unbonding stake.
+    pub fn in_good_standing(&self) -> bool {
+        !self.closed && self.stake_amount >= MIN_REPUTATION_STAKE_LAMPORTS
+    }
+
+    pub fn score(&self, now: i64) -> ReputationScore {
+        reputation_score_breakdown(
+            self.total_jobs_completed,
+            self.total_jobs_failed,
+            self.total_disputes_won,
+            self.total_disputes_lost,
+            self.stake_amount,
+            self.bayesian_rating,
+            now.saturating_sub(self.last_active),
+        )
+    }
+
+    /// Takes `bps` of the agent's stake, unbonding stake first, and returns the lamports
+    /// to move out of `rep_vault`.
+    pub fn slash(&mut self, bps: u64) -> u64 {
+        let total = self.stake_amount.saturating_add(self.unbonding_amount);
+        let amount = (total as u128 * bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        let from_unbonding = amount.min(self.unbonding_amount);
+        self.unbonding_amount -= from_unbonding;
+        self.stake_amount = self.stake_amount.saturating_sub(amount - from_unbonding);
+        amount
+    }
+
+    pub fn record_dispute(&mut self, won: bool, now: i64) {
+        if won {
+            self.total_disputes_won = self.total_disputes_won.saturating_add(1);
+        } else {
+            self.total_disputes_lost = self.total_disputes_lost.saturating_add(1);
+        }
+        self.last_active = now;
+    }
+}
+
+/// Reputation score (0-100) and the components it was computed from.
+/// Returned by `get_reputation_score` for CPI consumers.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReputationScore {
+    pub score: u8,
+    /// Completed jobs as a percentage of all settled jobs.
+    pub completion_rate: u8,
+    /// +2 per dispute won, -5 per dispute lost.
+    pub dispute_adjustment: i64,
+    pub volume_bonus: u8,
+    pub stake_bonus: u8,
+    /// Bayesian rating above or below the prior mean, 1 point per 0.2 stars.
+    pub rating_adjustment: i64,
+    /// Inactivity decay applied to the summed components, in basis points.
+    pub decay_multiplier: u16,
+    pub days_inactive: i64,
+}
+
+/// Loads the profile at an agent's `[b"reputation", agent]` PDA, or `None` if it was never opened.
+/// Permissionless settlement paths take the PDA itself rather than an optional account, so the
+/// caller cannot leave out a profile that exists.
+pub fn load_reputation(info: &AccountInfo) -> Result<Option<AgentReputation>> {
+    if info.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    let data = info.try_borrow_data()?;
+    Ok(Some(AgentReputation::try_deserialize(&mut &data[..])?))
+}
+
+/// Writes back a profile loaded with `load_reputation`.
+pub fn store_reputation(info: &AccountInfo, reputation: &Option<AgentReputation>) -> Result<()> {
+    if let Some(reputation) = reputation {
+        let mut data = info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        reputation.try_serialize(&mut writer)?;
+    }
+    Ok(())
+}
+
+/// Records a settled job on the provider's and the client's reputation, each if it exists;
+/// callers load them with `load_reputation`, so neither can be left out.
+/// A provider without a profile can still be paid; it just builds no record. A provider
+/// that accepted with its profile releases the job from `open_jobs` here.
+/// Only the provider is charged with a failure; the client's counters track work delivered to it.
+/// A categorized job also updates the provider's track for that category, which must be passed.
+pub fn record_settlement(
+    job: &JobEscrow,
+    provider: Option<&mut AgentReputation>,
+    client: Option<&mut AgentReputation>,
+    provider_category: Option<&mut CategoryReputation>,
+    delivered: bool,
+    volume: u64,
+    now: i64,
+) -> Result<()> {
+    if job.provider_has_reputation {
+        require!(provider.is_some(), TrustNetError::MissingProviderReputation);
+    }
+    // Volume is kept in lamports; token payments would mix in the mint's units.
+    let volume = job.lamport_value(volume);
+    if let Some(provider) = provider {
+        if job.provider_has_reputation {
+            provider.open_jobs = provider.open_jobs.saturating_sub(1);
+        }
+        if delivered {
+            provider.total_jobs_completed = provider.total_jobs_completed.saturating_add(1);
+            provider.total_volume = provider.total_volume.saturating_add(volume);
+        } else {
+            provider.total_jobs_failed = provider.total_jobs_failed.saturating_add(1);
+        }
+        provider.last_active = now;
+    }
+
+    if let Some(client) = client {
+        if delivered {
+            client.total_jobs_completed = client.total_jobs_completed.saturating_add(1);
+            client.total_volume = client.total_volume.saturating_add(volume);
+        }
+        client.last_active = now;
+    }
+
+    if job.category.is_some() {
+        let track = provider_category.ok_or(TrustNetError::MissingCategoryReputation)?;
+        track.record_job(delivered, volume, now);
+    }
+    Ok(())
+}
+
+/// `record_settlement` for instructions that take the provider's and the client's reputation
+/// PDAs: loads whichever profiles exist, records the job on them and writes them back.
+pub fn settle_reputations(
+    job: &JobEscrow,
+    provider_info: &AccountInfo,
+    client_info: &AccountInfo,
+    provider_category: Option<&mut CategoryReputation>,
+    delivered: bool,
+    volume: u64,
+    now: i64,
+) -> Result<()> {
+    let mut provider = load_reputation(provider_info)?;
+    let mut client = load_reputation(client_info)?;
+    record_settlement(job, provider.as_mut(), client.as_mut(), provider_category, delivered, volume, now)?;
+    store_reputation(provider_info, &provider)?;
+    store_reputation(client_info, &client)
+}
+
+/// An agent's record within one job category. Global `AgentReputation` counters
+/// still aggregate across all categories; this track only adds the per-category view.
+#[account]
+pub struct CategoryReputation {
+    pub agent: Pubkey,
+    pub category: u8,
+    pub jobs_completed: u64,
+    pub jobs_failed: u64,
+    pub volume: u64,
+    pub avg_rating: u16,
+    pub bayesian_rating: u16,
+    pub rating_count: u64,
+    pub rating_sum: u64,
+    pub created_at: i64,
+    pub last_active: i64,
+    pub bump: u8,
+}
+
+impl CategoryReputation {
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 8 + 2 + 2 + 8 + 8 + 8 + 8 + 1;
+
+    pub fn record_job(&mut self, delivered: bool, volume: u64, now: i64) {
+        if delivered {
+            self.jobs_completed = self.jobs_completed.saturating_add(1);
+            self.volume = self.volume.saturating_add(volume);
+        } else {
+            self.jobs_failed = self.jobs_failed.saturating_add(1);
+        }
+        self.last_active = now;
+    }
+
+    pub fn record_rating(&mut self, score: u8, now: i64) {
+        self.avg_rating = compute_rating_average(self.avg_rating, self.rating_count, score);
+        self.rating_count = self.rating_count.saturating_add(1);
+        self.rating_sum = self.rating_sum.saturating_add(score as u64 * 100);
+        self.bayesian_rating = bayesian_rating(self.rating_sum, self.rating_count);
+        self.last_active = now;
+    }
+
+    /// Scores the category record like `AgentReputation::score`. Disputes are not
+    /// tracked per category, and stake is the agent's global stake.
+    pub fn score(&self, stake_amount: u64, now: i64) -> ReputationScore {
+        reputation_score_breakdown(
+            self.jobs_completed,
+            self.jobs_failed,
+            0,
+            0,
+            stake_amount,
+            self.bayesian_rating,
+            now.saturating_sub(self.last_active),
+        )
+    }
 }
 
 #[account]
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::utils::constants::MAX_REVIEWERS;
+
+/// Reviewer panel for a `PeerReview` job. A named panel lists its reviewers up
+/// front; an open panel fills its seats with reputation holders as they review.
+#[account]
+pub struct ReviewPanel {
+    pub job: Pubkey,
+    pub named: bool,
+    pub reviewers: Vec<Pubkey>,
+    pub seats: u8,
+    pub specialization: Option<u8>,
+    pub bond: u64,
+    pub approvals: u8,
+    pub rejections: u8,
+    /// Protocol fee moved into the review vault to pay reviewers.
+    pub fee: u64,
+    pub outcome: Option<bool>,
+    pub bump: u8,
+}
+
+impl ReviewPanel {
+    pub const LEN: usize = 8 + 32 + 1 + 4 + 32 * MAX_REVIEWERS + 1 + 2 + 8 + 1 + 1 + 8 + 2 + 1;
+
+    pub fn majority(&self) -> u8 {
+        self.seats / 2 + 1
+    }
+
+    /// Reward for each reviewer on the winning side: their share of the fee plus the losing side's bonds.
+    pub fn reward_share(&self) -> u64 {
+        let (winners, losers) = match self.outcome {
+            Some(true) => (self.approvals, self.rejections),
+            Some(false) => (self.rejections, self.approvals),
+            None => return 0,
+        };
+        if winners == 0 {
+            return 0;
+        }
+        let pool = self.fee.saturating_add(self.bond.saturating_mul(losers as u64));
+        pool / winners as u64
+    }
+}
+
+#[account]
+pub struct Review {
+    pub job: Pubkey,
+    pub reviewer: Pubkey,
+    pub score: u8,
+    pub approved: bool,
+    pub comment_hash: [u8; 32],
+    pub claimed: bool,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl Review {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 1 + 32 + 1 + 8 + 1;
+}
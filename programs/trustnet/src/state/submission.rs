@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+/// One deliverable submitted by the provider. Every resubmission after a rejection or
+/// rework gets its own record, so the full history stays on-chain.
+#[account]
+pub struct Submission {
+    pub job: Pubkey,
+    pub provider: Pubkey,
+    pub index: u16,
+    pub milestone: Option<u8>,
+    pub submission_hash: [u8; 32],
+    pub submitted_at: i64,
+    pub bump: u8,
+}
+
+impl Submission {
+    pub const LEN: usize = 8 + 32 + 32 + 2 + 2 + 32 + 8 + 1;
+}
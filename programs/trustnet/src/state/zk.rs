@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::utils::constants::MAX_PUBLIC_INPUTS;
+use crate::utils::hashing::hash_bytes;
+
+/// Groth16 verifying key over alt_bn128, stored under its own hash so a
+/// `ZkVerify` job can commit to it in `verification_data[..32]`.
+/// Points use the big-endian encoding expected by the alt_bn128 syscalls.
+#[account]
+pub struct VerifyingKey {
+    pub hash: [u8; 32],
+    pub alpha_g1: [u8; 64],
+    pub beta_g2: [u8; 128],
+    pub gamma_g2: [u8; 128],
+    pub delta_g2: [u8; 128],
+    pub ic: Vec<[u8; 64]>,
+    pub bump: u8,
+}
+
+impl VerifyingKey {
+    pub const LEN: usize = 8 + 32 + 64 + 128 * 3 + 4 + 64 * (MAX_PUBLIC_INPUTS + 1) + 1;
+
+    pub fn compute_hash(
+        alpha_g1: &[u8; 64],
+        beta_g2: &[u8; 128],
+        gamma_g2: &[u8; 128],
+        delta_g2: &[u8; 128],
+        ic: &[[u8; 64]],
+    ) -> [u8; 32] {
+        let mut data = Vec::with_capacity(64 + 128 * 3 + 64 * ic.len());
+        data.extend_from_slice(alpha_g1);
+        data.extend_from_slice(beta_g2);
+        data.extend_from_slice(gamma_g2);
+        data.extend_from_slice(delta_g2);
+        for point in ic {
+            data.extend_from_slice(point);
+        }
+        hash_bytes(&data)
+    }
+}
@@ -5,9 +5,40 @@ pub const MIN_ARBITER_STAKE_LAMPORTS: u64 = 1_000_000_000; // 1 SOL
 pub const PROTOCOL_FEE_BPS: u64 = 10; // 0.1%
 pub const BPS_DENOMINATOR: u64 = 10_000;
 pub const DISPUTE_FEE_BPS: u64 = 100; // 1%
+pub const PROVIDER_SLASH_BPS: u64 = 1_000; // 10% of reputation stake on a lost dispute or expired job
+pub const SLASH_CLIENT_SHARE_BPS: u64 = 5_000; // 50% of slashed stake to the client, rest to treasury
 pub const GRACE_PERIOD_SECONDS: i64 = 60 * 60; // 1 hour
+pub const REVISION_WINDOW_SECONDS: i64 = 24 * 60 * 60; // minimum time to resubmit after a rejection
 pub const MAX_TAGS_LEN: usize = 64;
 pub const MAX_SPECIALIZATIONS_LEN: usize = 64;
+pub const MIN_MILESTONES: usize = 2;
+pub const MAX_MILESTONES: usize = 10;
+pub const MAX_REQUIRED_SPECIALIZATIONS: usize = 16;
+pub const MAX_ORACLES: usize = 10;
+pub const MAX_REVIEWERS: usize = 7;
+pub const REVIEW_PASS_SCORE: u8 = 3;
+pub const MAX_PUBLIC_INPUTS: usize = 8;
+pub const ARBITER_PANEL_SIZE: usize = 3;
+#[cfg(not(feature = "short-dispute-windows"))]
+pub const DISPUTE_COMMIT_SECONDS: i64 = 3600;
+#[cfg(not(feature = "short-dispute-windows"))]
+pub const DISPUTE_REVEAL_SECONDS: i64 = 3600;
+// Localnet test builds only: lets the test suite vote through a dispute without a clock warp.
+#[cfg(feature = "short-dispute-windows")]
+pub const DISPUTE_COMMIT_SECONDS: i64 = 20;
+#[cfg(feature = "short-dispute-windows")]
+pub const DISPUTE_REVEAL_SECONDS: i64 = 20;
+pub const ARBITER_SELECTION_DELAY_SLOTS: u64 = 8; // slots between opening a dispute and the slot hash that seeds its panel
+pub const ARBITER_ACCURACY_MAX: u16 = 1_000;
+pub const ARBITER_ACCURACY_ALPHA_BPS: u64 = 2_000; // weight of the latest case in the accuracy average
+pub const ARBITER_REGISTRY_PAGE_SIZE: usize = 16;
+pub const MAX_ARBITER_REGISTRY_PAGES: u32 = 16; // every page is passed to select_arbiters
+pub const RATING_PRIOR_MEAN: u64 = 300; // 3.00 stars, scaled like avg_rating
+pub const RATING_PRIOR_WEIGHT: u64 = 10; // phantom ratings at the prior mean
+pub const RATER_WEIGHT_BASE: u64 = 50; // rater factor without a reputation profile
+pub const REPUTATION_UNBONDING_SECONDS: i64 = 14 * 86_400; // delay before unstaked lamports can be withdrawn
+pub const ARBITER_WITHDRAW_COOLDOWN_SECONDS: i64 = 7 * 86_400; // slashing window after an arbiter deactivates
+pub const REPUTATION_HALF_LIFE_SECONDS: i64 = 730 * 86_400; // score halves after 2 years inactive
 
 pub fn now_ts(clock: &Clock) -> i64 {
     clock.unix_timestamp
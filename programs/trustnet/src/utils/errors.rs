@@ -26,4 +26,64 @@ pub enum TrustNetError {
     RatingOutOfRange,
     #[msg("Too many bytes provided")]
     BytesTooLarge,
+    #[msg("Provider must co-sign to cancel an active job")]
+    ProviderConsentRequired,
+    #[msg("Token account required for mint-denominated job")]
+    MissingTokenAccount,
+    #[msg("Token account or mint does not match the job mint")]
+    MintMismatch,
+    #[msg("Invalid milestone")]
+    InvalidMilestone,
+    #[msg("Provider does not meet the job requirements")]
+    ProviderNotEligible,
+    #[msg("Bidding is not open for this job")]
+    BiddingClosed,
+    #[msg("Invalid bid reveal")]
+    InvalidBidReveal,
+    #[msg("No valid bid to award")]
+    NoValidBid,
+    #[msg("Invalid oracle committee")]
+    InvalidOracleCommittee,
+    #[msg("Invalid review panel")]
+    InvalidReviewPanel,
+    #[msg("Reviewer is not eligible for this panel")]
+    ReviewerNotEligible,
+    #[msg("Invalid verifying key")]
+    InvalidVerifyingKey,
+    #[msg("Invalid zk proof")]
+    InvalidProof,
+    #[msg("Provider has no reputation track for the job category")]
+    MissingCategoryReputation,
+    #[msg("Stake is still unbonding")]
+    StakeUnbonding,
+    #[msg("Arbiter was not drawn for this dispute")]
+    ArbiterNotSelected,
+    #[msg("Arbiter accounts do not match the registry")]
+    InvalidArbiterSet,
+    #[msg("Arbiter registry is full")]
+    ArbiterRegistryFull,
+    #[msg("Arbiter registry page is full")]
+    ArbiterRegistryPageFull,
+    #[msg("Existing arbiter registry pages still have room")]
+    ArbiterRegistryPageNotFull,
+    #[msg("Arbiter is not active")]
+    ArbiterInactive,
+    #[msg("Arbiter must deactivate before withdrawing")]
+    ArbiterActive,
+    #[msg("Arbiter still has unresolved disputes")]
+    ArbiterHasOpenCases,
+    #[msg("Commit phase has ended")]
+    CommitPhaseClosed,
+    #[msg("Reveal phase has ended")]
+    RevealPhaseClosed,
+    #[msg("Provider accepted with its reputation profile, which must be passed")]
+    MissingProviderReputation,
+    #[msg("Stake cannot unbond while the agent has open jobs")]
+    OpenJobsPending,
+    #[msg("The slot that seeds the arbiter draw has not been reached")]
+    SelectionSlotPending,
+    #[msg("No registered arbiter is eligible for this dispute")]
+    NoEligibleArbiters,
+    #[msg("Reputation account is not the agent's reputation PDA")]
+    ReputationAccountMismatch,
 }
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::utils::errors::TrustNetError;
+
+pub fn transfer_from_vault<'info>(
+    vault: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    let (pda, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+    require!(pda == vault.key(), TrustNetError::Unauthorized);
+    let signer_seeds: &[&[u8]] = &[seeds[0], seeds[1], &[bump]];
+    let transfer = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        system_program::Transfer {
+            from: vault.clone(),
+            to: to.clone(),
+        },
+        &[signer_seeds],
+    );
+    system_program::transfer(transfer, amount)?;
+    Ok(())
+}
+
+/// Transfers `amount` from a signer-owned token account into a PDA-owned
+/// vault and returns what the vault actually received after any Token-2022
+/// transfer fee.
+pub fn deposit_tokens<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    vault: &mut InterfaceAccount<'info, TokenAccount>,
+    authority: &Signer<'info>,
+    amount: u64,
+) -> Result<u64> {
+    require_keys_eq!(from.mint, mint.key(), TrustNetError::MintMismatch);
+    require_keys_eq!(vault.mint, mint.key(), TrustNetError::MintMismatch);
+    let before = vault.amount;
+    let transfer = CpiContext::new(
+        token_program.to_account_info(),
+        TransferChecked {
+            from: from.to_account_info(),
+            mint: mint.to_account_info(),
+            to: vault.to_account_info(),
+            authority: authority.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(transfer, amount, mint.decimals)?;
+    vault.reload()?;
+    let received = vault.amount.checked_sub(before).ok_or(TrustNetError::InsufficientFunds)?;
+    require!(received > 0, TrustNetError::InsufficientFunds);
+    Ok(received)
+}
+
+/// PDA-owned token account holding funds for a mint-denominated job or dispute.
+pub struct TokenVault<'a, 'info> {
+    pub vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'a, 'info> TokenVault<'a, 'info> {
+    /// Returns `None` for lamport jobs and the validated token accounts otherwise.
+    pub fn for_mint(
+        job_mint: Option<Pubkey>,
+        vault: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+        mint: &'a Option<InterfaceAccount<'info, Mint>>,
+        token_program: &'a Option<Interface<'info, TokenInterface>>,
+    ) -> Result<Option<Self>> {
+        let Some(job_mint) = job_mint else {
+            return Ok(None);
+        };
+        let vault = vault.as_ref().ok_or(TrustNetError::MissingTokenAccount)?;
+        let mint = mint.as_ref().ok_or(TrustNetError::MissingTokenAccount)?;
+        let token_program = token_program.as_ref().ok_or(TrustNetError::MissingTokenAccount)?;
+        require_keys_eq!(mint.key(), job_mint, TrustNetError::MintMismatch);
+        require_keys_eq!(vault.mint, job_mint, TrustNetError::MintMismatch);
+        require_keys_eq!(*mint.to_account_info().owner, token_program.key(), TrustNetError::MintMismatch);
+        Ok(Some(Self { vault, mint, token_program }))
+    }
+}
+
+/// A lamport vault PDA (`seeds = [prefix, parent]`) together with its token
+/// account when the job is mint-denominated. Payouts go through `pay`, which
+/// picks the system or token transfer accordingly.
+pub struct Escrow<'a, 'info> {
+    prefix: &'static [u8],
+    parent: Pubkey,
+    vault: &'a AccountInfo<'info>,
+    system_program: &'a Program<'info, System>,
+    token: Option<TokenVault<'a, 'info>>,
+}
+
+impl<'a, 'info> Escrow<'a, 'info> {
+    pub fn new(
+        prefix: &'static [u8],
+        parent: Pubkey,
+        vault: &'a AccountInfo<'info>,
+        system_program: &'a Program<'info, System>,
+        token: Option<TokenVault<'a, 'info>>,
+    ) -> Self {
+        Self { prefix, parent, vault, system_program, token }
+    }
+
+    pub fn balance(&self) -> u64 {
+        match &self.token {
+            Some(token) => token.vault.amount,
+            None => self.vault.lamports(),
+        }
+    }
+
+    /// Pays `wallet`, or its token account for mint-denominated escrows.
+    pub fn pay(
+        &self,
+        wallet: &AccountInfo<'info>,
+        token_account: &Option<InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<()> {
+        match &self.token {
+            Some(_) => {
+                let token_account = token_account.as_ref().ok_or(TrustNetError::MissingTokenAccount)?;
+                self.pay_to(&token_account.to_account_info(), &wallet.key(), amount)
+            }
+            None => self.pay_to(wallet, &wallet.key(), amount),
+        }
+    }
+
+    /// Pays a raw recipient account: the wallet itself for lamport escrows,
+    /// or a token account owned by `owner` for mint-denominated ones.
+    pub fn pay_to(&self, recipient: &AccountInfo<'info>, owner: &Pubkey, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let seeds: &[&[u8]] = &[self.prefix, self.parent.as_ref()];
+        let Some(token) = &self.token else {
+            require_keys_eq!(recipient.key(), *owner, TrustNetError::Unauthorized);
+            return transfer_from_vault(self.vault, recipient, self.system_program, seeds, amount);
+        };
+
+        let to = InterfaceAccount::<TokenAccount>::try_from(recipient)?;
+        require_keys_eq!(to.mint, token.mint.key(), TrustNetError::MintMismatch);
+        require_keys_eq!(to.owner, *owner, TrustNetError::Unauthorized);
+        let (pda, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+        require!(pda == self.vault.key() && token.vault.owner == pda, TrustNetError::Unauthorized);
+        let signer_seeds: &[&[u8]] = &[seeds[0], seeds[1], &[bump]];
+        // The vault is debited exactly `amount` even when a Token-2022 transfer
+        // fee is withheld from the recipient, so liabilities stay in sync.
+        let transfer = CpiContext::new_with_signer(
+            token.token_program.to_account_info(),
+            TransferChecked {
+                from: token.vault.to_account_info(),
+                mint: token.mint.to_account_info(),
+                to: recipient.clone(),
+                authority: self.vault.clone(),
+            },
+            &[signer_seeds],
+        );
+        token_interface::transfer_checked(transfer, amount, token.mint.decimals)
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::alt_bn128::prelude::{
+    alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing,
+};
+
+use crate::state::zk::VerifyingKey;
+use crate::utils::errors::TrustNetError;
+
+/// alt_bn128 base field modulus q, big-endian.
+const BASE_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/// alt_bn128 scalar field modulus r, big-endian. Public inputs must be below it.
+const SCALAR_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Groth16Proof {
+    pub a: [u8; 64],
+    pub b: [u8; 128],
+    pub c: [u8; 64],
+}
+
+/// Checks e(-A, B) · e(alpha, beta) · e(vk_x, gamma) · e(C, delta) == 1,
+/// where vk_x = ic[0] + Σ input[i] · ic[i + 1].
+pub fn verify(vk: &VerifyingKey, proof: &Groth16Proof, public_inputs: &[[u8; 32]]) -> Result<bool> {
+    require!(public_inputs.len() + 1 == vk.ic.len(), TrustNetError::InvalidProof);
+
+    let mut vk_x = vk.ic[0];
+    for (input, ic) in public_inputs.iter().zip(vk.ic[1..].iter()) {
+        require!(*input < SCALAR_FIELD_MODULUS, TrustNetError::InvalidProof);
+        let mut mul_input = [0u8; 96];
+        mul_input[..64].copy_from_slice(ic);
+        mul_input[64..].copy_from_slice(input);
+        let product = alt_bn128_multiplication(&mul_input).map_err(|_| TrustNetError::InvalidProof)?;
+
+        let mut add_input = [0u8; 128];
+        add_input[..64].copy_from_slice(&vk_x);
+        add_input[64..].copy_from_slice(&product);
+        let sum = alt_bn128_addition(&add_input).map_err(|_| TrustNetError::InvalidProof)?;
+        vk_x.copy_from_slice(&sum);
+    }
+
+    let mut pairing_input = Vec::with_capacity(192 * 4);
+    pairing_input.extend_from_slice(&negate_g1(&proof.a));
+    pairing_input.extend_from_slice(&proof.b);
+    pairing_input.extend_from_slice(&vk.alpha_g1);
+    pairing_input.extend_from_slice(&vk.beta_g2);
+    pairing_input.extend_from_slice(&vk_x);
+    pairing_input.extend_from_slice(&vk.gamma_g2);
+    pairing_input.extend_from_slice(&proof.c);
+    pairing_input.extend_from_slice(&vk.delta_g2);
+
+    let result = alt_bn128_pairing(&pairing_input).map_err(|_| TrustNetError::InvalidProof)?;
+    Ok(result.last() == Some(&1) && result[..result.len() - 1].iter().all(|b| *b == 0))
+}
+
+/// Negates a G1 point by replacing y with q - y (the identity stays as is).
+fn negate_g1(point: &[u8; 64]) -> [u8; 64] {
+    let mut out = *point;
+    let y = &point[32..];
+    if y.iter().all(|b| *b == 0) {
+        return out;
+    }
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let mut diff = BASE_FIELD_MODULUS[i] as i16 - y[i] as i16 - borrow;
+        borrow = if diff < 0 {
+            diff += 256;
+            1
+        } else {
+            0
+        };
+        out[32 + i] = diff as u8;
+    }
+    out
+}
@@ -10,6 +10,26 @@ pub fn hash_bytes(data: &[u8]) -> [u8; 32] {
     out
 }
 
+pub fn hash_bid(bidder: &Pubkey, job: &Pubkey, amount: u64, salt: &[u8]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(32 + 32 + 8 + salt.len());
+    data.extend_from_slice(bidder.as_ref());
+    data.extend_from_slice(job.as_ref());
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(salt);
+    hash_bytes(&data)
+}
+
+/// Public input that binds a ZkVerify proof to one job: the hash of the job key and the
+/// client's `verification_data[32..]`, with the top byte cleared so it is a valid alt_bn128 scalar.
+pub fn zk_job_binding(job: &Pubkey, expected_output: &[u8]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(32 + expected_output.len());
+    data.extend_from_slice(job.as_ref());
+    data.extend_from_slice(expected_output);
+    let mut binding = hash_bytes(&data);
+    binding[0] = 0;
+    binding
+}
+
 pub fn hash_vote(arbiter: &Pubkey, dispute: &Pubkey, vote: bool, salt: &[u8]) -> [u8; 32] {
     let mut data = Vec::with_capacity(32 + 32 + 1 + salt.len());
     data.extend_from_slice(arbiter.as_ref());
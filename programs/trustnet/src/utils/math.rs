@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::state::reputation::ReputationScore;
+use crate::utils::constants::{
+    ARBITER_ACCURACY_ALPHA_BPS, ARBITER_ACCURACY_MAX, BPS_DENOMINATOR, RATER_WEIGHT_BASE, RATING_PRIOR_MEAN, RATING_PRIOR_WEIGHT, REPUTATION_HALF_LIFE_SECONDS,
+};
+
 pub fn integer_sqrt(value: u64) -> u64 {
     let mut x = value;
     let mut y = (x + 1) / 2;
@@ -17,18 +22,70 @@ pub fn compute_rating_average(current_avg: u16, current_count: u64, new_score: u
     (new_total / new_count) as u16
 }
 
+/// Bayesian average rating, scaled like `avg_rating`: the ratings are blended with
+/// `RATING_PRIOR_WEIGHT` phantom ratings at `RATING_PRIOR_MEAN`, so a handful of ratings
+/// stays close to the prior while many ratings converge on their own mean.
+pub fn bayesian_rating(rating_sum: u64, rating_count: u64) -> u16 {
+    let prior = RATING_PRIOR_MEAN * RATING_PRIOR_WEIGHT;
+    let total = prior.saturating_add(rating_sum);
+    (total / RATING_PRIOR_WEIGHT.saturating_add(rating_count)) as u16
+}
+
+/// Weight of one rating: the job's lamport value times a rater factor of `RATER_WEIGHT_BASE`
+/// plus the rater's own score (0-100), when the rater has a reputation profile. A job with no
+/// lamport value (a mint-denominated one) counts as 1 lamport, so it weighs by the rater alone.
+pub fn rating_weight(job_lamports: u64, rater_score: Option<u8>) -> u128 {
+    let factor = RATER_WEIGHT_BASE + rater_score.unwrap_or(0) as u64;
+    job_lamports.max(1) as u128 * factor as u128
+}
+
+/// Exponential moving average of arbiter accuracy: each case moves the score
+/// `ARBITER_ACCURACY_ALPHA_BPS` of the way toward `ARBITER_ACCURACY_MAX` (majority) or 0.
+pub fn arbiter_accuracy_ema(current: u16, correct: bool) -> u16 {
+    let target = if correct { ARBITER_ACCURACY_MAX as u64 } else { 0 };
+    let kept = current as u64 * (BPS_DENOMINATOR - ARBITER_ACCURACY_ALPHA_BPS);
+    ((kept + target * ARBITER_ACCURACY_ALPHA_BPS) / BPS_DENOMINATOR) as u16
+}
+
+/// Vote weight: the square root of stake, scaled from 0.5x at zero accuracy through
+/// 1x at the starting score of 500 to 1.5x at full accuracy.
+pub fn arbiter_vote_weight(stake: u64, accuracy_score: u16) -> u64 {
+    let scale = ARBITER_ACCURACY_MAX as u64;
+    integer_sqrt(stake).saturating_mul(scale / 2 + accuracy_score.min(ARBITER_ACCURACY_MAX) as u64) / scale
+}
+
 pub fn clamp_u8(value: i64, min: i64, max: i64) -> u8 {
     value.clamp(min, max) as u8
 }
 
-pub fn forgetting_multiplier(days_inactive: i64) -> u16 {
-    match days_inactive {
-        d if d <= 30 => 100,
-        d if d <= 90 => 95,
-        d if d <= 180 => 90,
-        d if d <= 360 => 80,
-        _ => 70,
+/// 2^(-1/2^(k+1)) in Q32 for k = 0..15, one entry per fractional bit of elapsed half-lives.
+const HALF_LIFE_ROOTS_Q32: [u64; 16] = [
+    3_037_000_500, 3_611_622_603, 3_938_502_376, 4_112_874_773,
+    4_202_935_003, 4_248_701_965, 4_271_771_996, 4_283_353_945,
+    4_289_156_690, 4_292_061_010, 4_293_513_907, 4_294_240_540,
+    4_294_603_903, 4_294_785_595, 4_294_876_445, 4_294_921_870,
+];
+
+/// Continuous half-life decay in basis points: 10_000 with no inactivity, halving every
+/// `half_life_secs`. Integer-only so off-chain ports reproduce it exactly.
+pub fn decay_multiplier_bps(inactive_secs: i64, half_life_secs: i64) -> u16 {
+    if inactive_secs <= 0 || half_life_secs <= 0 {
+        return BPS_DENOMINATOR as u16;
     }
+    let halvings = inactive_secs / half_life_secs;
+    if halvings >= 32 {
+        return 0;
+    }
+    // Remainder of the current half-life, in 1/65536ths.
+    let frac = ((inactive_secs % half_life_secs) as u128 * 65_536 / half_life_secs as u128) as u32;
+    let mut multiplier: u128 = 1 << 32;
+    for (k, root) in HALF_LIFE_ROOTS_Q32.iter().enumerate() {
+        if frac & (1 << (15 - k)) != 0 {
+            multiplier = (multiplier * *root as u128) >> 32;
+        }
+    }
+    multiplier >>= halvings;
+    ((multiplier * BPS_DENOMINATOR as u128) >> 32) as u16
 }
 
 pub fn compute_reputation_score(
@@ -37,16 +94,37 @@ pub fn compute_reputation_score(
     disputes_won: u64,
     disputes_lost: u64,
     stake_lamports: u64,
-    days_inactive: i64,
+    bayesian_rating: u16,
+    inactive_secs: i64,
 ) -> u8 {
+    reputation_score_breakdown(
+        completed,
+        failed,
+        disputes_won,
+        disputes_lost,
+        stake_lamports,
+        bayesian_rating,
+        inactive_secs,
+    )
+    .score
+}
+
+pub fn reputation_score_breakdown(
+    completed: u64,
+    failed: u64,
+    disputes_won: u64,
+    disputes_lost: u64,
+    stake_lamports: u64,
+    bayesian_rating: u16,
+    inactive_secs: i64,
+) -> ReputationScore {
     let total_jobs = completed + failed;
-    let mut base = if total_jobs == 0 {
+    let completion_rate = if total_jobs == 0 {
         0
     } else {
-        (completed * 100 / total_jobs) as i64
+        (completed * 100 / total_jobs) as u8
     };
-    base += (disputes_won as i64) * 2;
-    base -= (disputes_lost as i64) * 5;
+    let dispute_adjustment = (disputes_won as i64) * 2 - (disputes_lost as i64) * 5;
     let volume_bonus = if completed >= 50 {
         15
     } else if completed >= 20 {
@@ -56,7 +134,6 @@ pub fn compute_reputation_score(
     } else {
         0
     };
-    base += volume_bonus;
     let stake_sol = stake_lamports / 1_000_000_000;
     let stake_bonus = if stake_sol >= 50 {
         10
@@ -67,8 +144,18 @@ pub fn compute_reputation_score(
     } else {
         0
     };
-    base += stake_bonus;
-    let multiplier = forgetting_multiplier(days_inactive) as i64;
-    let adjusted = base * multiplier / 100;
-    clamp_u8(adjusted, 0, 100)
+    let rating_adjustment = (bayesian_rating as i64 - RATING_PRIOR_MEAN as i64) / 20;
+    let base = completion_rate as i64 + dispute_adjustment + volume_bonus as i64 + stake_bonus as i64 + rating_adjustment;
+    let decay_multiplier = decay_multiplier_bps(inactive_secs, REPUTATION_HALF_LIFE_SECONDS);
+    let adjusted = base * decay_multiplier as i64 / BPS_DENOMINATOR as i64;
+    ReputationScore {
+        score: clamp_u8(adjusted, 0, 100),
+        completion_rate,
+        dispute_adjustment,
+        volume_bonus,
+        stake_bonus,
+        rating_adjustment,
+        decay_multiplier,
+        days_inactive: inactive_secs.max(0) / 86_400,
+    }
 }
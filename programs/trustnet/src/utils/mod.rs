@@ -3,3 +3,5 @@ pub mod errors;
 pub mod math;
 pub mod hashing;
 pub mod time;
+pub mod escrow;
+pub mod groth16;
@@ -1,11 +1,26 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAccount,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMint,
+  getAccount,
+  getMintLen,
+  getTransferFeeAmount,
+  mintTo,
+} from "@solana/spl-token";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
 import { assert, expect } from "chai";
 import crypto from "crypto";
 
 // Load IDL (generated by anchor build)
 import { Trustnet } from "../target/types/trustnet";
+import { zkJobBinding } from "../packages/sdk/src/instructions";
+import { computeScore, decayMultiplierBps } from "../packages/sdk/src/reputation";
 
 const SOL = LAMPORTS_PER_SOL;
 
@@ -38,6 +53,69 @@ describe("trustnet", () => {
     PublicKey.findProgramAddressSync([Buffer.from("reputation"), agent.toBuffer()], program.programId);
   const findRepVaultPda = (agent: PublicKey) =>
     PublicKey.findProgramAddressSync([Buffer.from("rep_vault"), agent.toBuffer()], program.programId);
+  const findCategoryReputationPda = (agent: PublicKey, category: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("category_rep"), agent.toBuffer(), Buffer.from([category])],
+      program.programId
+    );
+  const findSubmissionPda = (job: PublicKey, index: number) => {
+    const indexLe = Buffer.alloc(2);
+    indexLe.writeUInt16LE(index);
+    return PublicKey.findProgramAddressSync([Buffer.from("submission"), job.toBuffer(), indexLe], program.programId);
+  };
+  const findJobTokenVaultPda = (job: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("job_token_vault"), job.toBuffer()], program.programId);
+
+  // Mint-denominated jobs
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const TRANSFER_FEE_BPS = 100; // 1% withheld by the Token-2022 test mint on every transfer
+  const createTransferFeeMint = async () => {
+    const mint = Keypair.generate();
+    const space = getMintLen([ExtensionType.TransferFeeConfig]);
+    const tx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: payer.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        mint.publicKey,
+        payer.publicKey,
+        payer.publicKey,
+        TRANSFER_FEE_BPS,
+        BigInt(1_000_000_000),
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(mint.publicKey, 6, payer.publicKey, null, TOKEN_2022_PROGRAM_ID)
+    );
+    await provider.sendAndConfirm(tx, [mint]);
+    return mint.publicKey;
+  };
+  // Token accounts for the client, provider and treasury PDA; the client and provider are funded.
+  const setupTokenAccounts = async (tokenProgram: PublicKey) => {
+    const mint = tokenProgram.equals(TOKEN_2022_PROGRAM_ID)
+      ? await createTransferFeeMint()
+      : await createMint(provider.connection, payer, payer.publicKey, null, 6, undefined, undefined, tokenProgram);
+    const tokenAccount = (owner: PublicKey) =>
+      createAccount(provider.connection, payer, mint, owner, Keypair.generate(), undefined, tokenProgram);
+    const clientToken = await tokenAccount(client.publicKey);
+    const providerToken = await tokenAccount(providerKp.publicKey);
+    const treasuryToken = await tokenAccount(findTreasuryPda()[0]);
+    for (const account of [clientToken, providerToken]) {
+      await mintTo(provider.connection, payer, mint, account, payer, 10_000_000, [], undefined, tokenProgram);
+    }
+    return { mint, tokenProgram, tokenAccount, clientToken, providerToken, treasuryToken };
+  };
+  type Tokens = Awaited<ReturnType<typeof setupTokenAccounts>>;
+  // Everything credited to a token account, including any transfer fee withheld in it.
+  const grossBalance = async (account: PublicKey, tokenProgram: PublicKey) => {
+    const info = await getAccount(provider.connection, account, undefined, tokenProgram);
+    return Number(info.amount + (getTransferFeeAmount(info)?.withheldAmount ?? BigInt(0)));
+  };
+  const tokenBalance = async (account: PublicKey, tokenProgram: PublicKey) =>
+    Number((await getAccount(provider.connection, account, undefined, tokenProgram)).amount);
 
   beforeEach(async () => {
     client = Keypair.generate();
@@ -73,6 +151,229 @@ describe("trustnet", () => {
       assert.equal(repAccount.stakeAmount.toNumber(), stakeLamports.toNumber());
     });
 
+    it("returns the score and its breakdown through return data", async () => {
+      const [reputation] = findReputationPda(client.publicKey);
+      const [repVault] = findRepVaultPda(client.publicKey);
+
+      await program.methods
+        .initReputation(new anchor.BN(2 * SOL), [])
+        .accounts({
+          agent: client.publicKey,
+          reputation,
+          repVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      const score = await program.methods.getReputationScore().accounts({ reputation }).view();
+      assert.equal(score.completionRate, 0);
+      assert.equal(score.stakeBonus, 2);
+      assert.equal(score.decayMultiplier, 10_000);
+      assert.equal(score.score, 2);
+
+      // The SDK port reproduces the on-chain score exactly.
+      const repAccount = await program.account.agentReputation.fetch(reputation);
+      const view = {
+        agent: repAccount.agent,
+        totalJobsCompleted: BigInt(repAccount.totalJobsCompleted.toString()),
+        totalJobsFailed: BigInt(repAccount.totalJobsFailed.toString()),
+        totalDisputesWon: BigInt(repAccount.totalDisputesWon.toString()),
+        totalDisputesLost: BigInt(repAccount.totalDisputesLost.toString()),
+        totalVolume: BigInt(repAccount.totalVolume.toString()),
+        avgRating: repAccount.avgRating,
+        bayesianRating: repAccount.bayesianRating,
+        weightedAvgRating: repAccount.weightedAvgRating,
+        ratingCount: BigInt(repAccount.ratingCount.toString()),
+        stakeAmount: BigInt(repAccount.stakeAmount.toString()),
+        unbondingAmount: BigInt(repAccount.unbondingAmount.toString()),
+        unbondingReadyAt: repAccount.unbondingReadyAt.toNumber(),
+        closed: repAccount.closed,
+        openJobs: repAccount.openJobs,
+        lastActive: repAccount.lastActive.toNumber(),
+      };
+      assert.equal(computeScore(view, repAccount.lastActive.toNumber()), score.score);
+      assert.equal(decayMultiplierBps(730 * 86_400), 5_000);
+    });
+
+    it("unbonds stake before it can be withdrawn, closing the profile below the minimum", async () => {
+      const [reputation] = findReputationPda(client.publicKey);
+      const [repVault] = findRepVaultPda(client.publicKey);
+      const accounts = {
+        agent: client.publicKey,
+        reputation,
+        repVault,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      };
+      await program.methods.initReputation(new anchor.BN(2 * SOL), []).accounts(accounts).signers([client]).rpc();
+
+      // Leaving less than the 0.1 SOL minimum is only possible by unbonding everything
+      try {
+        await program.methods.requestUnstake(new anchor.BN(1.95 * SOL)).accounts(accounts).signers([client]).rpc();
+        assert.fail("Expected minimum stake error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("MinimumStakeNotMet");
+      }
+
+      await program.methods.requestUnstake(new anchor.BN(1.5 * SOL)).accounts(accounts).signers([client]).rpc();
+      let repAccount = await program.account.agentReputation.fetch(reputation);
+      assert.equal(repAccount.stakeAmount.toNumber(), 0.5 * SOL);
+      assert.equal(repAccount.unbondingAmount.toNumber(), 1.5 * SOL);
+      assert.isFalse(repAccount.closed);
+
+      // Unbonding stake no longer earns the stake bonus, and stays in the vault until the delay passes
+      const score = await program.methods.getReputationScore().accounts({ reputation }).view();
+      assert.equal(score.stakeBonus, 0);
+      try {
+        await program.methods.withdrawStake().accounts(accounts).signers([client]).rpc();
+        assert.fail("Expected unbonding error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("StakeUnbonding");
+      }
+      assert.isAtLeast(await provider.connection.getBalance(repVault), 2 * SOL);
+
+      await program.methods.requestUnstake(new anchor.BN(0.5 * SOL)).accounts(accounts).signers([client]).rpc();
+      repAccount = await program.account.agentReputation.fetch(reputation);
+      assert.equal(repAccount.stakeAmount.toNumber(), 0);
+      assert.equal(repAccount.unbondingAmount.toNumber(), 2 * SOL);
+      assert.isTrue(repAccount.closed);
+    });
+
+    it("tracks a categorized job on the provider's category reputation", async () => {
+      const category = 7;
+      const jobId = randomBytes(32);
+      const [job] = findJobPda(jobId);
+      const [jobVault] = findJobVaultPda(job);
+      const [treasury] = findTreasuryPda();
+      const [providerRep] = findReputationPda(providerKp.publicKey);
+      const [providerCategoryRep] = findCategoryReputationPda(providerKp.publicKey, category);
+
+      await program.methods
+        .initReputation(new anchor.BN(0.1 * SOL), [])
+        .accounts({
+          agent: providerKp.publicKey,
+          reputation: providerRep,
+          repVault: findRepVaultPda(providerKp.publicKey)[0],
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      await program.methods
+        .createJob(
+          jobId,
+          new anchor.BN(0.5 * SOL),
+          new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+          { clientApproval: {} },
+          Array(64).fill(0),
+          sha256("terms")
+        )
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+      await program.methods.setJobCategory(category).accounts({ client: client.publicKey, job }).signers([client]).rpc();
+
+      // Without a track for the category the provider cannot take the job
+      try {
+        await program.methods
+          .acceptJob(new anchor.BN(0.1 * SOL))
+          .accounts({
+            provider: providerKp.publicKey,
+            job,
+            providerCategoryReputation: null,
+            jobVault,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([providerKp])
+          .rpc();
+        assert.fail("Expected missing category reputation");
+      } catch (err: any) {
+        expect(err.toString()).to.include("MissingCategoryReputation");
+      }
+
+      await program.methods
+        .initCategoryReputation(category)
+        .accounts({
+          agent: providerKp.publicKey,
+          reputation: providerRep,
+          categoryReputation: providerCategoryRep,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          providerCategoryReputation: providerCategoryRep,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .submitCompletion(sha256("done"))
+        .accounts({ provider: providerKp.publicKey, job, submission: findSubmissionPda(job, 0)[0] })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          client: client.publicKey,
+          job,
+          jobVault,
+          treasury,
+          provider: providerKp.publicKey,
+          providerReputation: providerRep,
+          providerCategoryReputation: providerCategoryRep,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      const [rating] = PublicKey.findProgramAddressSync(
+        [Buffer.from("rating"), Buffer.from(jobId), client.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .rateJob(jobId, 5, [], sha256("great work"))
+        .accounts({
+          rater: client.publicKey,
+          job,
+          rateeReputation: providerRep,
+          rateeCategoryReputation: providerCategoryRep,
+          rating,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      const track = await program.account.categoryReputation.fetch(providerCategoryRep);
+      assert.equal(track.jobsCompleted.toNumber(), 1);
+      assert.equal(track.volume.toNumber(), 0.5 * SOL);
+      assert.equal(track.ratingCount.toNumber(), 1);
+      assert.equal(track.bayesianRating, 318);
+
+      // Global counters still aggregate the job
+      const repAccount = await program.account.agentReputation.fetch(providerRep);
+      assert.equal(repAccount.totalJobsCompleted.toNumber(), 1);
+
+      const score = await program.methods
+        .getCategoryScore()
+        .accounts({ reputation: providerRep, categoryReputation: providerCategoryRep })
+        .view();
+      assert.equal(score.completionRate, 100);
+      assert.equal(score.disputeAdjustment.toNumber(), 0);
+    });
+
     it("fails to init reputation below minimum stake", async () => {
       const [reputation] = findReputationPda(client.publicKey);
       const [repVault] = findRepVaultPda(client.publicKey);
@@ -215,7 +516,28 @@ describe("trustnet", () => {
       // Record provider balance before approval
       const providerBalBefore = await provider.connection.getBalance(providerKp.publicKey);
 
-      // Approve
+      // Approve. The client cannot leave its profile out by passing another account.
+      const [providerRep] = findReputationPda(providerKp.publicKey);
+      const [clientRep] = findReputationPda(client.publicKey);
+      try {
+        await program.methods
+          .approveCompletion()
+          .accounts({
+            client: client.publicKey,
+            job,
+            jobVault,
+            treasury,
+            provider: providerKp.publicKey,
+            providerReputation: providerRep,
+            clientReputation: findReputationPda(Keypair.generate().publicKey)[0],
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([client])
+          .rpc();
+        assert.fail("Expected substituted client reputation to be refused");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ConstraintSeeds");
+      }
       await program.methods
         .approveCompletion()
         .accounts({
@@ -224,6 +546,8 @@ describe("trustnet", () => {
           jobVault,
           treasury,
           provider: providerKp.publicKey,
+          providerReputation: providerRep,
+          clientReputation: clientRep,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([client])
@@ -237,6 +561,60 @@ describe("trustnet", () => {
       const expectedPayout = amount.toNumber() + providerStake.toNumber();
       const fee = Math.floor(amount.toNumber() * 10 / 10000); // 0.1% fee
       assert.approximately(providerBalAfter - providerBalBefore, expectedPayout - fee, 10000); // Allow for rounding
+
+      // Settlement updates both parties' reputation
+      const providerRepAccount = await program.account.agentReputation.fetch(providerRep);
+      assert.equal(providerRepAccount.totalJobsCompleted.toNumber(), 1);
+      assert.equal(providerRepAccount.totalVolume.toNumber(), amount.toNumber());
+      const clientRepAccount = await program.account.agentReputation.fetch(clientRep);
+      assert.equal(clientRepAccount.totalJobsCompleted.toNumber(), 1);
+    });
+
+    it("pays a provider that never opened a reputation profile", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const newcomer = Keypair.generate();
+      await provider.connection.confirmTransaction(await provider.connection.requestAirdrop(newcomer.publicKey, SOL));
+
+      await program.methods
+        .createJob(jobId, amount, deadline, { clientApproval: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: newcomer.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({ provider: newcomer.publicKey, job, jobVault, systemProgram: anchor.web3.SystemProgram.programId })
+        .signers([newcomer])
+        .rpc();
+      await program.methods
+        .submitCompletion(sha256("submission"))
+        .accounts({ provider: newcomer.publicKey, job })
+        .signers([newcomer])
+        .rpc();
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          client: client.publicKey,
+          job,
+          jobVault,
+          treasury,
+          provider: newcomer.publicKey,
+          providerReputation: findReputationPda(newcomer.publicKey)[0],
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      expect(jobAccount.status).to.deep.equal({ completed: {} });
+      assert.equal(await provider.connection.getBalance(jobVault), 0);
     });
 
     it("prevents non-client from approving", async () => {
@@ -296,53 +674,108 @@ describe("trustnet", () => {
         expect(err.toString()).to.include("Unauthorized");
       }
     });
-  });
-
-  describe("ratings", () => {
-    it("allows client to rate provider after job completion", async () => {
-      const jobId = randomBytes(32);
-      const [job] = findJobPda(jobId);
-      const [jobVault] = findJobVaultPda(job);
-      const [treasury] = findTreasuryPda();
-      const [providerRep] = findReputationPda(providerKp.publicKey);
 
-      // Setup: init reputation
-      const [clientRep] = findReputationPda(client.publicKey);
-      const [clientRepVault] = findRepVaultPda(client.publicKey);
-      const [providerRepVault] = findRepVaultPda(providerKp.publicKey);
+    it("lets the client request one revision before a rejection opens a dispute and keeps every submission", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const verificationData = Array(64).fill(0);
+      verificationData[0] = 1; // one revision round
+      const [dispute] = PublicKey.findProgramAddressSync([Buffer.from("dispute"), job.toBuffer()], program.programId);
+      const [disputeVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dispute_vault"), dispute.toBuffer()],
+        program.programId
+      );
 
       await program.methods
-        .initReputation(new anchor.BN(0.1 * SOL), [])
+        .createJob(jobId, amount, deadline, { clientApproval: {} }, verificationData, sha256("terms"))
         .accounts({
-          agent: client.publicKey,
-          reputation: clientRep,
-          repVault: clientRepVault,
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([client])
         .rpc();
 
       await program.methods
-        .initReputation(new anchor.BN(0.1 * SOL), [])
+        .acceptJob(new anchor.BN(0.1 * SOL))
         .accounts({
-          agent: providerKp.publicKey,
-          reputation: providerRep,
-          repVault: providerRepVault,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([providerKp])
         .rpc();
 
-      // Complete a job
+      for (const round of [0, 1]) {
+        await program.methods
+          .submitCompletion(sha256(`submission-${round}`))
+          .accounts({ provider: providerKp.publicKey, job })
+          .signers([providerKp])
+          .rpc();
+
+        const escalate = round === 1;
+        if (!escalate) {
+          // Under the cap the rejection reopens the job, so it cannot create the dispute PDA
+          try {
+            await program.methods
+              .rejectSubmission(sha256(`feedback-${round}`))
+              .accounts({
+                client: client.publicKey,
+                job,
+                dispute,
+                jobVault,
+                disputeVault,
+                systemProgram: anchor.web3.SystemProgram.programId,
+              })
+              .signers([client])
+              .rpc();
+            assert.fail("Expected dispute accounts to be refused");
+          } catch (err: any) {
+            expect(err.toString()).to.include("InvalidDisputeState");
+          }
+        }
+        await program.methods
+          .rejectSubmission(sha256(`feedback-${round}`))
+          .accounts({
+            client: client.publicKey,
+            job,
+            dispute: escalate ? dispute : null,
+            jobVault,
+            disputeVault: escalate ? disputeVault : null,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([client])
+          .rpc();
+      }
+
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      assert.equal(jobAccount.revisionCount, 1);
+      expect(jobAccount.status).to.deep.equal({ disputed: {} });
+      const disputeAccount = await program.account.dispute.fetch(dispute);
+      assert.equal(disputeAccount.job.toBase58(), job.toBase58());
+
+      // Both rounds stay on-chain and the dispute points at the last one.
+      assert.equal(jobAccount.submissionCount, 2);
+      for (const round of [0, 1]) {
+        const [submission] = findSubmissionPda(job, round);
+        const submissionAccount = await program.account.submission.fetch(submission);
+        assert.deepEqual(submissionAccount.submissionHash, sha256(`submission-${round}`));
+      }
+      assert.equal(disputeAccount.submissionIndex, 1);
+      assert.deepEqual(disputeAccount.submissionHash, sha256("submission-1"));
+    });
+
+    it("gives the provider a revision window when on-time work is rejected after the deadline", async () => {
+      // Deadline already passed by the time the client rejects
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) - 60);
+      const verificationData = Array(64).fill(0);
+      verificationData[0] = 1; // one revision round
       await program.methods
-        .createJob(
-          jobId,
-          new anchor.BN(0.5 * SOL),
-          new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
-          { clientApproval: {} },
-          Array(64).fill(0),
-          sha256("terms")
-        )
+        .createJob(jobId, new anchor.BN(0.5 * SOL), deadline, { clientApproval: {} }, verificationData, sha256("terms"))
         .accounts({
           client: client.publicKey,
           provider: providerKp.publicKey,
@@ -353,7 +786,6 @@ describe("trustnet", () => {
         })
         .signers([client])
         .rpc();
-
       await program.methods
         .acceptJob(new anchor.BN(0.1 * SOL))
         .accounts({
@@ -364,48 +796,2409 @@ describe("trustnet", () => {
         })
         .signers([providerKp])
         .rpc();
-
       await program.methods
-        .submitCompletion(sha256("done"))
+        .submitCompletion(sha256("submission"))
         .accounts({ provider: providerKp.publicKey, job })
         .signers([providerKp])
         .rpc();
-
       await program.methods
-        .approveCompletion()
+        .rejectSubmission(sha256("feedback"))
         .accounts({
           client: client.publicKey,
           job,
+          dispute: null,
+          jobVault,
+          disputeVault: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      expect(jobAccount.status).to.deep.equal({ active: {} });
+      assert.isAtLeast(jobAccount.deadline.toNumber(), Math.floor(Date.now() / 1000) + 86_400 - 60);
+
+      // The rejection cannot be followed by an expiry that refunds the client and slashes the provider
+      try {
+        await program.methods
+          .expireJob()
+          .accounts({
+            job,
+            jobVault,
+            treasury,
+            client: client.publicKey,
+            provider: providerKp.publicKey,
+            providerReputation: findReputationPda(providerKp.publicKey)[0],
+            providerRepVault: findRepVaultPda(providerKp.publicKey)[0],
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Expected the revision window to hold off expiry");
+      } catch (err: any) {
+        expect(err.toString()).to.include("DeadlineNotReached");
+      }
+    });
+
+    it("reopens the job with a revision window when the oracle asks for rework after the deadline", async () => {
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) - 60);
+      const oracle = Keypair.generate();
+      await provider.connection.confirmTransaction(await provider.connection.requestAirdrop(oracle.publicKey, SOL));
+      const verificationData = [...oracle.publicKey.toBytes(), ...Array(32).fill(0)]; // rework on rejection
+
+      await program.methods
+        .createJob(jobId, new anchor.BN(0.5 * SOL), deadline, { oracleVerify: {} }, verificationData, sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .submitCompletion(sha256("submission"))
+        .accounts({ provider: providerKp.publicKey, job })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .oracleVerify(false, sha256("notes"))
+        .accounts({
+          oracle: oracle.publicKey,
+          job,
           jobVault,
           treasury,
           provider: providerKp.publicKey,
+          client: client.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([oracle])
+        .rpc();
+
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      expect(jobAccount.status).to.deep.equal({ active: {} });
+      assert.isAtLeast(jobAccount.deadline.toNumber(), Math.floor(Date.now() / 1000) + 86_400 - 60);
+      try {
+        await program.methods
+          .expireJob()
+          .accounts({
+            job,
+            jobVault,
+            treasury,
+            client: client.publicKey,
+            provider: providerKp.publicKey,
+            providerReputation: findReputationPda(providerKp.publicKey)[0],
+            providerRepVault: findRepVaultPda(providerKp.publicKey)[0],
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Expected the revision window to hold off expiry");
+      } catch (err: any) {
+        expect(err.toString()).to.include("DeadlineNotReached");
+      }
+    });
+
+    it("fails the job and refunds the client when the oracle rejects with the refund action", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const oracle = Keypair.generate();
+      await provider.connection.confirmTransaction(await provider.connection.requestAirdrop(oracle.publicKey, SOL));
+      const verificationData = [...oracle.publicKey.toBytes(), ...Array(32).fill(0)];
+      verificationData[32] = 1; // refund on rejection
+
+      await program.methods
+        .createJob(jobId, amount, deadline, { oracleVerify: {} }, verificationData, sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([client])
         .rpc();
 
-      // Rate
-      const [rating] = PublicKey.findProgramAddressSync(
-        [Buffer.from("rating"), Buffer.from(jobId), client.publicKey.toBuffer()],
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      await program.methods
+        .submitCompletion(sha256("submission"))
+        .accounts({ provider: providerKp.publicKey, job })
+        .signers([providerKp])
+        .rpc();
+
+      await program.methods
+        .oracleVerify(false, sha256("notes"))
+        .accounts({
+          oracle: oracle.publicKey,
+          job,
+          jobVault,
+          treasury,
+          provider: providerKp.publicKey,
+          client: client.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([oracle])
+        .rpc();
+
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      expect(jobAccount.status).to.deep.equal({ failed: {} });
+      expect(jobAccount.oracleNotesHash).to.deep.equal(sha256("notes"));
+      assert.equal(await provider.connection.getBalance(jobVault), 0);
+    });
+
+    it("pays out an OracleThreshold job once M of N oracles approve", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const oracles = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      for (const oracle of oracles) {
+        await provider.connection.confirmTransaction(await provider.connection.requestAirdrop(oracle.publicKey, SOL));
+      }
+      const [committee] = PublicKey.findProgramAddressSync(
+        [Buffer.from("oracle_committee"), job.toBuffer()],
         program.programId
       );
 
       await program.methods
-        .rateJob(jobId, 5, [1, 2], sha256("great work"))
+        .createJob(jobId, amount, deadline, { oracleThreshold: {} }, Array(64).fill(0), sha256("terms"))
         .accounts({
-          rater: client.publicKey,
+          client: client.publicKey,
+          provider: providerKp.publicKey,
           job,
-          rateeReputation: providerRep,
-          rating,
+          jobVault,
+          treasury,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([client])
         .rpc();
 
-      // Check reputation updated
-      const repAccount = await program.account.agentReputation.fetch(providerRep);
-      assert.equal(repAccount.ratingCount.toNumber(), 1);
-      assert.equal(repAccount.avgRating, 500); // 5.00 * 100
+      await program.methods
+        .setOracleCommittee(oracles.map((o) => o.publicKey), 2)
+        .accounts({
+          client: client.publicKey,
+          job,
+          committee,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          oracleCommittee: committee,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      await program.methods
+        .submitCompletion(sha256("submission"))
+        .accounts({ provider: providerKp.publicKey, job })
+        .signers([providerKp])
+        .rpc();
+
+      // An attestation that does not escalate cannot create the job's dispute PDA
+      const [dispute] = PublicKey.findProgramAddressSync([Buffer.from("dispute"), job.toBuffer()], program.programId);
+      try {
+        await program.methods
+          .attestOracle(false, sha256("notes"))
+          .accounts({
+            oracle: oracles[0].publicKey,
+            job,
+            committee,
+            attestation: PublicKey.findProgramAddressSync(
+              [Buffer.from("attestation"), job.toBuffer(), oracles[0].publicKey.toBuffer(), Buffer.from([0, 0])],
+              program.programId
+            )[0],
+            jobVault,
+            treasury,
+            provider: providerKp.publicKey,
+            client: client.publicKey,
+            dispute,
+            disputeVault: PublicKey.findProgramAddressSync(
+              [Buffer.from("dispute_vault"), dispute.toBuffer()],
+              program.programId
+            )[0],
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([oracles[0]])
+          .rpc();
+        assert.fail("Expected dispute accounts to be refused");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidDisputeState");
+      }
+      assert.isNull(await provider.connection.getAccountInfo(dispute));
+
+      for (const [i, oracle] of oracles.slice(0, 2).entries()) {
+        const [attestation] = PublicKey.findProgramAddressSync(
+          [Buffer.from("attestation"), job.toBuffer(), oracle.publicKey.toBuffer(), Buffer.from([0, 0])],
+          program.programId
+        );
+        await program.methods
+          .attestOracle(true, sha256(`notes-${i}`))
+          .accounts({
+            oracle: oracle.publicKey,
+            job,
+            committee,
+            attestation,
+            jobVault,
+            treasury,
+            provider: providerKp.publicKey,
+            client: client.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([oracle])
+          .rpc();
+
+        const jobAccount = await program.account.jobEscrow.fetch(job);
+        expect(jobAccount.status).to.deep.equal(i === 0 ? { submitted: {} } : { completed: {} });
+      }
+    });
+
+    it("refuses to accept an OracleThreshold job before its committee is set", async () => {
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      await program.methods
+        .createJob(jobId, new anchor.BN(0.5 * SOL), deadline, { oracleThreshold: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      try {
+        await program.methods
+          .acceptJob(new anchor.BN(0.1 * SOL))
+          .accounts({
+            provider: providerKp.publicKey,
+            job,
+            oracleCommittee: null,
+            jobVault,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([providerKp])
+          .rpc();
+        assert.fail("Expected missing committee error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidOracleCommittee");
+      }
+    });
+
+    it("settles a PeerReview job once a majority of the panel approves", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const reviewers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      for (const reviewer of reviewers) {
+        await provider.connection.confirmTransaction(await provider.connection.requestAirdrop(reviewer.publicKey, SOL));
+      }
+      const [panel] = PublicKey.findProgramAddressSync([Buffer.from("review_panel"), job.toBuffer()], program.programId);
+      const [reviewVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("review_vault"), job.toBuffer()],
+        program.programId
+      );
+      const [providerRep] = findReputationPda(providerKp.publicKey);
+
+      await program.methods
+        .createJob(jobId, amount, deadline, { peerReview: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      // Without a panel the job could never settle, so it cannot be accepted yet
+      try {
+        await program.methods
+          .acceptJob(new anchor.BN(0.1 * SOL))
+          .accounts({
+            provider: providerKp.publicKey,
+            job,
+            reviewPanel: null,
+            jobVault,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([providerKp])
+          .rpc();
+        assert.fail("Expected missing review panel error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidReviewPanel");
+      }
+
+      await program.methods
+        .setReviewPanel(reviewers.map((r) => r.publicKey), 0, null, new anchor.BN(0.01 * SOL))
+        .accounts({
+          client: client.publicKey,
+          job,
+          panel,
+          reviewVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          reviewPanel: panel,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      await program.methods
+        .submitCompletion(sha256("submission"))
+        .accounts({ provider: providerKp.publicKey, job })
+        .signers([providerKp])
+        .rpc();
+
+      for (const [i, reviewer] of reviewers.slice(0, 2).entries()) {
+        const [review] = PublicKey.findProgramAddressSync(
+          [Buffer.from("review"), job.toBuffer(), reviewer.publicKey.toBuffer()],
+          program.programId
+        );
+        await program.methods
+          .submitReview(4, sha256(`review-${i}`))
+          .accounts({
+            reviewer: reviewer.publicKey,
+            job,
+            panel,
+            review,
+            providerReputation: providerRep,
+            reviewVault,
+            jobVault,
+            provider: providerKp.publicKey,
+            client: client.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([reviewer])
+          .rpc();
+      }
+
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      expect(jobAccount.status).to.deep.equal({ completed: {} });
+      const rep = await program.account.agentReputation.fetch(providerRep);
+      assert.equal(rep.totalJobsCompleted.toNumber(), 1);
+    });
+
+    it("refunds reviewer bonds when a dispute ends review before a majority", async () => {
+      const bond = new anchor.BN(0.01 * SOL);
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const reviewers = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      await provider.connection.confirmTransaction(await provider.connection.requestAirdrop(reviewers[0].publicKey, SOL));
+      const [panel] = PublicKey.findProgramAddressSync([Buffer.from("review_panel"), job.toBuffer()], program.programId);
+      const [reviewVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("review_vault"), job.toBuffer()],
+        program.programId
+      );
+      const [review] = PublicKey.findProgramAddressSync(
+        [Buffer.from("review"), job.toBuffer(), reviewers[0].publicKey.toBuffer()],
+        program.programId
+      );
+      const [dispute] = PublicKey.findProgramAddressSync([Buffer.from("dispute"), job.toBuffer()], program.programId);
+      const [disputeVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dispute_vault"), dispute.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .createJob(jobId, new anchor.BN(0.5 * SOL), deadline, { peerReview: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+      await program.methods
+        .setReviewPanel(reviewers.map((r) => r.publicKey), 0, null, bond)
+        .accounts({ client: client.publicKey, job, panel, reviewVault, systemProgram: anchor.web3.SystemProgram.programId })
+        .signers([client])
+        .rpc();
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          reviewPanel: panel,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .submitCompletion(sha256("submission"))
+        .accounts({ provider: providerKp.publicKey, job })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .submitReview(2, sha256("review-0"))
+        .accounts({
+          reviewer: reviewers[0].publicKey,
+          job,
+          panel,
+          review,
+          providerReputation: findReputationPda(providerKp.publicKey)[0],
+          reviewVault,
+          jobVault,
+          provider: providerKp.publicKey,
+          client: client.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([reviewers[0]])
+        .rpc();
+
+      const claim = () =>
+        program.methods
+          .claimReviewReward()
+          .accounts({
+            reviewer: reviewers[0].publicKey,
+            job,
+            panel,
+            review,
+            reviewVault,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([reviewers[0]])
+          .rpc();
+      try {
+        await claim();
+        assert.fail("Expected the bond to stay locked while the panel is still reviewing");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidStatus");
+      }
+
+      await program.methods
+        .raiseDispute(Buffer.from("not what was agreed"), sha256("evidence"))
+        .accounts({
+          raiser: client.publicKey,
+          job,
+          dispute,
+          jobVault,
+          disputeVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      const before = await provider.connection.getBalance(reviewers[0].publicKey);
+      await claim();
+      const after = await provider.connection.getBalance(reviewers[0].publicKey);
+      assert.equal(after - before, bond.toNumber());
+    });
+
+    it("rejects a review panel with an even number of seats", async () => {
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const [panel] = PublicKey.findProgramAddressSync([Buffer.from("review_panel"), job.toBuffer()], program.programId);
+      const [reviewVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("review_vault"), job.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .createJob(jobId, new anchor.BN(0.5 * SOL), deadline, { peerReview: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      try {
+        await program.methods
+          .setReviewPanel([], 4, null, new anchor.BN(0.01 * SOL))
+          .accounts({ client: client.publicKey, job, panel, reviewVault, systemProgram: anchor.web3.SystemProgram.programId })
+          .signers([client])
+          .rpc();
+        assert.fail("Expected invalid review panel error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidReviewPanel");
+      }
+    });
+
+    it("keeps a reviewer with a closed profile out of an open seat", async () => {
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const reviewer = Keypair.generate();
+      await provider.connection.confirmTransaction(await provider.connection.requestAirdrop(reviewer.publicKey, SOL));
+      const [reviewerRep] = findReputationPda(reviewer.publicKey);
+      const [reviewerRepVault] = findRepVaultPda(reviewer.publicKey);
+      const [panel] = PublicKey.findProgramAddressSync([Buffer.from("review_panel"), job.toBuffer()], program.programId);
+      const [reviewVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("review_vault"), job.toBuffer()],
+        program.programId
+      );
+      const [review] = PublicKey.findProgramAddressSync(
+        [Buffer.from("review"), job.toBuffer(), reviewer.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .initReputation(new anchor.BN(0.1 * SOL), [])
+        .accounts({
+          agent: reviewer.publicKey,
+          reputation: reviewerRep,
+          repVault: reviewerRepVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([reviewer])
+        .rpc();
+      // Unbonding the whole stake closes the profile
+      await program.methods
+        .requestUnstake(new anchor.BN(0.1 * SOL))
+        .accounts({
+          agent: reviewer.publicKey,
+          reputation: reviewerRep,
+          repVault: reviewerRepVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([reviewer])
+        .rpc();
+
+      await program.methods
+        .createJob(jobId, new anchor.BN(0.5 * SOL), deadline, { peerReview: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+      await program.methods
+        .setReviewPanel([], 1, null, new anchor.BN(0.01 * SOL))
+        .accounts({ client: client.publicKey, job, panel, reviewVault, systemProgram: anchor.web3.SystemProgram.programId })
+        .signers([client])
+        .rpc();
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          reviewPanel: panel,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .submitCompletion(sha256("submission"))
+        .accounts({ provider: providerKp.publicKey, job })
+        .signers([providerKp])
+        .rpc();
+
+      try {
+        await program.methods
+          .submitReview(4, sha256("review"))
+          .accounts({
+            reviewer: reviewer.publicKey,
+            job,
+            panel,
+            review,
+            reviewerReputation: reviewerRep,
+            providerReputation: findReputationPda(providerKp.publicKey)[0],
+            reviewVault,
+            jobVault,
+            provider: providerKp.publicKey,
+            client: client.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([reviewer])
+          .rpc();
+        assert.fail("Expected reviewer not eligible error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ReviewerNotEligible");
+      }
+    });
+
+    it("rejects a ZK proof whose public input is not bound to the job", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const [alphaG1, betaG2, gammaG2, deltaG2] = [64, 128, 128, 128].map((n) => Array(n).fill(0));
+      const ic = [Array(64).fill(0), Array(64).fill(0)];
+      const vkHash = Array.from(
+        crypto
+          .createHash("sha256")
+          .update(Buffer.from([...alphaG1, ...betaG2, ...gammaG2, ...deltaG2, ...ic.flat()]))
+          .digest()
+      );
+      const [verifyingKey] = PublicKey.findProgramAddressSync(
+        [Buffer.from("zk_vk"), Buffer.from(vkHash)],
+        program.programId
+      );
+
+      await program.methods
+        .registerVerifyingKey(alphaG1, betaG2, gammaG2, deltaG2, ic)
+        .accounts({ payer: client.publicKey, verifyingKey, systemProgram: anchor.web3.SystemProgram.programId })
+        .signers([client])
+        .rpc();
+
+      const verificationData = [...vkHash, ...sha256("expected-output")];
+      await program.methods
+        .createJob(jobId, amount, deadline, { zkVerify: {} }, verificationData, sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      try {
+        await program.methods
+          .submitZkProof(
+            { a: Array(64).fill(0), b: Array(128).fill(0), c: Array(64).fill(0) },
+            // Bound to another job with the same expected output
+            [Array.from(zkJobBinding(Keypair.generate().publicKey, Buffer.from(sha256("expected-output"))))]
+          )
+          .accounts({
+            provider: providerKp.publicKey,
+            job,
+            verifyingKey,
+            jobVault,
+            treasury,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([providerKp])
+          .rpc();
+        assert.fail("Expected invalid proof error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidProof");
+      }
+
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      expect(jobAccount.status).to.deep.equal({ active: {} });
+    });
+
+    it("cancels a created job and refunds the client", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+
+      await program.methods
+        .createJob(jobId, amount, deadline, { clientApproval: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      await program.methods
+        .cancelJob()
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      expect(jobAccount.status).to.deep.equal({ cancelled: {} });
+      assert.equal(await provider.connection.getBalance(jobVault), 0);
+    });
+
+    it("requires provider consent to cancel an active job", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+
+      await program.methods
+        .createJob(jobId, amount, deadline, { clientApproval: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      try {
+        await program.methods
+          .cancelJob()
+          .accounts({
+            client: client.publicKey,
+            provider: providerKp.publicKey,
+            job,
+            jobVault,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([client])
+          .rpc();
+        assert.fail("Expected provider consent error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ProviderConsentRequired");
+      }
+    });
+
+    it("refunds the client and returns the provider stake when the provider co-signs a cancel", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const providerStake = new anchor.BN(0.1 * SOL);
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+
+      await program.methods
+        .createJob(jobId, amount, deadline, { clientApproval: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+      await program.methods
+        .acceptJob(providerStake)
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      const clientBefore = await provider.connection.getBalance(client.publicKey);
+      const providerBefore = await provider.connection.getBalance(providerKp.publicKey);
+      await program.methods
+        .cancelJob()
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client, providerKp])
+        .rpc();
+
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      expect(jobAccount.status).to.deep.equal({ cancelled: {} });
+      assert.equal((await provider.connection.getBalance(client.publicKey)) - clientBefore, amount.toNumber());
+      assert.equal((await provider.connection.getBalance(providerKp.publicKey)) - providerBefore, providerStake.toNumber());
+    });
+
+    it("slashes the provider's reputation stake when an active job expires", async () => {
+      const [providerRep] = findReputationPda(providerKp.publicKey);
+      const [providerRepVault] = findRepVaultPda(providerKp.publicKey);
+      await program.methods
+        .initReputation(new anchor.BN(1 * SOL), [])
+        .accounts({
+          agent: providerKp.publicKey,
+          reputation: providerRep,
+          repVault: providerRepVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      // Deadline already passed, so the job can expire as soon as it is accepted
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) - 60);
+      await program.methods
+        .createJob(jobId, new anchor.BN(0.5 * SOL), deadline, { clientApproval: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      // The provider accepted without its profile, which still cannot be left out of the expiry
+      assert.isFalse((await program.account.jobEscrow.fetch(job)).providerHasReputation);
+      const clientBefore = await provider.connection.getBalance(client.publicKey);
+      const vaultBefore = await provider.connection.getBalance(providerRepVault);
+      await program.methods
+        .expireJob()
+        .accounts({
+          job,
+          jobVault,
+          treasury,
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          providerReputation: providerRep,
+          providerRepVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      // 10% of the 1 SOL stake is slashed; half of it compensates the client on top of the refund
+      const clientAfter = await provider.connection.getBalance(client.publicKey);
+      assert.equal(clientAfter - clientBefore, 0.5 * SOL + 0.1 * SOL + 0.05 * SOL);
+      assert.equal(vaultBefore - (await provider.connection.getBalance(providerRepVault)), 0.1 * SOL);
+      const repAccount = await program.account.agentReputation.fetch(providerRep);
+      assert.equal(repAccount.stakeAmount.toNumber(), 0.9 * SOL);
+      assert.equal(repAccount.totalJobsFailed.toNumber(), 1);
+    });
+
+    it("slashes the share of a missed milestone when it expires", async () => {
+      const [providerRep] = findReputationPda(providerKp.publicKey);
+      const [providerRepVault] = findRepVaultPda(providerKp.publicKey);
+      await program.methods
+        .initReputation(new anchor.BN(1 * SOL), [])
+        .accounts({
+          agent: providerKp.publicKey,
+          reputation: providerRep,
+          repVault: providerRepVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      const now = Math.floor(Date.now() / 1000);
+      const deadline = new anchor.BN(now + 3600);
+      await program.methods
+        .createJob(jobId, new anchor.BN(0.5 * SOL), deadline, { clientApproval: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+      // The first milestone is already overdue; the second is not
+      await program.methods
+        .defineMilestones([
+          { amount: new anchor.BN(0.2 * SOL), deadline: new anchor.BN(now - 60), termsHash: sha256("m0") },
+          { amount: new anchor.BN(0.3 * SOL), deadline, termsHash: sha256("m1") },
+        ])
+        .accounts({ client: client.publicKey, job })
+        .signers([client])
+        .rpc();
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          providerReputation: providerRep,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      const clientBefore = await provider.connection.getBalance(client.publicKey);
+      const vaultBefore = await provider.connection.getBalance(providerRepVault);
+      await program.methods
+        .expireJob()
+        .accounts({
+          job,
+          jobVault,
+          treasury,
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          providerReputation: providerRep,
+          providerRepVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      // The missed milestone is 40% of the job, so 40% of the 10% slash: 0.04 SOL, half to the client
+      assert.equal((await provider.connection.getBalance(client.publicKey)) - clientBefore, 0.2 * SOL + 0.02 * SOL);
+      assert.equal(vaultBefore - (await provider.connection.getBalance(providerRepVault)), 0.04 * SOL);
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      expect(jobAccount.status).to.deep.equal({ active: {} });
+    });
+
+    it("assigns an open job to the first provider that accepts", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const [providerRep] = findReputationPda(providerKp.publicKey);
+
+      await program.methods
+        .createJob(jobId, amount, deadline, { clientApproval: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: PublicKey.default,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          providerReputation: providerRep,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      assert.equal(jobAccount.provider.toBase58(), providerKp.publicKey.toBase58());
+      expect(jobAccount.status).to.deep.equal({ active: {} });
+    });
+
+    it("blocks unbonding while the provider has an open job", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const [providerRep] = findReputationPda(providerKp.publicKey);
+      const [providerRepVault] = findRepVaultPda(providerKp.publicKey);
+
+      await program.methods
+        .createJob(jobId, amount, deadline, { clientApproval: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          providerReputation: providerRep,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+      assert.equal((await program.account.agentReputation.fetch(providerRep)).openJobs, 1);
+
+      const unstake = () =>
+        program.methods
+          .requestUnstake(new anchor.BN(0.1 * SOL))
+          .accounts({
+            agent: providerKp.publicKey,
+            reputation: providerRep,
+            repVault: providerRepVault,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([providerKp])
+          .rpc();
+      try {
+        await unstake();
+        assert.fail("Expected open jobs error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("OpenJobsPending");
+      }
+
+      await program.methods
+        .submitCompletion(sha256("submission"))
+        .accounts({ provider: providerKp.publicKey, job })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          client: client.publicKey,
+          job,
+          jobVault,
+          treasury,
+          provider: providerKp.publicKey,
+          providerReputation: providerRep,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+      assert.equal((await program.account.agentReputation.fetch(providerRep)).openJobs, 0);
+      await unstake();
+    });
+
+    it("rejects providers that do not meet the job requirements", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      const [providerRep] = findReputationPda(providerKp.publicKey);
+
+      await program.methods
+        .createJob(jobId, amount, deadline, { clientApproval: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: PublicKey.default,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      await program.methods
+        .setJobRequirements({ minReputationScore: 0, minStake: new anchor.BN(1 * SOL), specializations: [] })
+        .accounts({ client: client.publicKey, job })
+        .signers([client])
+        .rpc();
+
+      try {
+        await program.methods
+          .acceptJob(new anchor.BN(0.1 * SOL))
+          .accounts({
+            provider: providerKp.publicKey,
+            job,
+            providerReputation: providerRep,
+            jobVault,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([providerKp])
+          .rpc();
+        assert.fail("Expected eligibility error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ProviderNotEligible");
+      }
+    });
+
+    it("keeps bids sealed until the commit phase ends", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const now = Math.floor(Date.now() / 1000);
+      const deadline = new anchor.BN(now + 3600);
+      const bidAmount = new anchor.BN(0.4 * SOL);
+      const salt = randomBytes(16);
+      const [bid] = PublicKey.findProgramAddressSync(
+        [Buffer.from("bid"), job.toBuffer(), providerKp.publicKey.toBuffer()],
+        program.programId
+      );
+      const amountLe = Buffer.alloc(8);
+      amountLe.writeBigUInt64LE(BigInt(bidAmount.toString()));
+      const bidHash = Array.from(
+        crypto
+          .createHash("sha256")
+          .update(Buffer.concat([providerKp.publicKey.toBuffer(), job.toBuffer(), amountLe, Buffer.from(salt)]))
+          .digest()
+      );
+
+      await program.methods
+        .createJob(jobId, amount, deadline, { clientApproval: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: PublicKey.default,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      await program.methods
+        .openBidding(new anchor.BN(now + 600), new anchor.BN(now + 1200))
+        .accounts({ client: client.publicKey, job })
+        .signers([client])
+        .rpc();
+
+      await program.methods
+        .commitBid(bidHash)
+        .accounts({
+          bidder: providerKp.publicKey,
+          job,
+          bid,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      assert.equal(jobAccount.auction.bidCount, 1);
+
+      try {
+        await program.methods
+          .revealBid(bidAmount, Buffer.from(salt))
+          .accounts({ bidder: providerKp.publicKey, job, bid })
+          .signers([providerKp])
+          .rpc();
+        assert.fail("Expected bidding closed error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("BiddingClosed");
+      }
+    });
+
+    it("releases milestones independently and completes once all settle", async () => {
+      const amount = new anchor.BN(0.5 * SOL);
+      const now = Math.floor(Date.now() / 1000);
+      const deadline = new anchor.BN(now + 3600);
+
+      await program.methods
+        .createJob(jobId, amount, deadline, { clientApproval: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      await program.methods
+        .defineMilestones([
+          { amount: new anchor.BN(0.2 * SOL), deadline: new anchor.BN(now + 1800), termsHash: sha256("m0") },
+          { amount: new anchor.BN(0.3 * SOL), deadline, termsHash: sha256("m1") },
+        ])
+        .accounts({ client: client.publicKey, job })
+        .signers([client])
+        .rpc();
+
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      for (const index of [0, 1]) {
+        await program.methods
+          .submitMilestone(index, sha256(`submission-${index}`))
+          .accounts({ provider: providerKp.publicKey, job })
+          .signers([providerKp])
+          .rpc();
+
+        await program.methods
+          .approveMilestone(index)
+          .accounts({
+            client: client.publicKey,
+            job,
+            jobVault,
+            treasury,
+            provider: providerKp.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([client])
+          .rpc();
+
+        const jobAccount = await program.account.jobEscrow.fetch(job);
+        expect(jobAccount.milestones[index].status).to.deep.equal({ released: {} });
+        expect(jobAccount.status).to.deep.equal(index === 0 ? { active: {} } : { completed: {} });
+      }
+
+      assert.equal(await provider.connection.getBalance(jobVault), 0);
+    });
+
+    it("rejects milestones on jobs settled by an oracle", async () => {
+      const deadline = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+      await program.methods
+        .createJob(jobId, new anchor.BN(0.5 * SOL), deadline, { oracleVerify: {} }, Array(64).fill(0), sha256("terms"))
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      try {
+        await program.methods
+          .defineMilestones([
+            { amount: new anchor.BN(0.2 * SOL), deadline, termsHash: sha256("m0") },
+            { amount: new anchor.BN(0.3 * SOL), deadline, termsHash: sha256("m1") },
+          ])
+          .accounts({ client: client.publicKey, job })
+          .signers([client])
+          .rpc();
+        assert.fail("Expected invalid milestone error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidMilestone");
+      }
+    });
+  });
+
+  describe("token escrow", () => {
+    let jobId: number[];
+    let job: PublicKey;
+    let jobVault: PublicKey;
+    let jobTokenVault: PublicKey;
+    let treasury: PublicKey;
+
+    beforeEach(() => {
+      jobId = randomBytes(32);
+      [job] = findJobPda(jobId);
+      [jobVault] = findJobVaultPda(job);
+      [jobTokenVault] = findJobTokenVaultPda(job);
+      [treasury] = findTreasuryPda();
+    });
+
+    const createTokenJob = (tokens: Tokens, amount: number, deadline: number, clientTokenAccount = tokens.clientToken) =>
+      program.methods
+        .createJob(
+          jobId,
+          new anchor.BN(amount),
+          new anchor.BN(deadline),
+          { clientApproval: {} },
+          Array(64).fill(0),
+          sha256("terms")
+        )
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          mint: tokens.mint,
+          clientTokenAccount,
+          jobTokenVault,
+          tokenProgram: tokens.tokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+    const acceptTokenJob = (tokens: Tokens, stake: number) =>
+      program.methods
+        .acceptJob(new anchor.BN(stake))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          mint: tokens.mint,
+          providerTokenAccount: tokens.providerToken,
+          jobTokenVault,
+          tokenProgram: tokens.tokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+    const settlementTokenAccounts = (tokens: Tokens) => ({
+      mint: tokens.mint,
+      jobTokenVault,
+      clientTokenAccount: tokens.clientToken,
+      providerTokenAccount: tokens.providerToken,
+      treasuryTokenAccount: tokens.treasuryToken,
+      tokenProgram: tokens.tokenProgram,
+    });
+    // The vault must always hold exactly what the job still owes: the escrowed amount plus the provider's stake.
+    const assertVaultMatchesLiabilities = async (tokens: Tokens) => {
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      assert.equal(
+        await tokenBalance(jobTokenVault, tokens.tokenProgram),
+        jobAccount.amount.toNumber() + jobAccount.providerStake.toNumber()
+      );
+      return jobAccount;
+    };
+
+    it("escrows an SPL token job and pays the provider and treasury in the mint", async () => {
+      const tokens = await setupTokenAccounts(TOKEN_PROGRAM_ID);
+      const [providerRep] = findReputationPda(providerKp.publicKey);
+      await program.methods
+        .initReputation(new anchor.BN(0.1 * SOL), [])
+        .accounts({
+          agent: providerKp.publicKey,
+          reputation: providerRep,
+          repVault: findRepVaultPda(providerKp.publicKey)[0],
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+      await createTokenJob(tokens, 1_000_000, Math.floor(Date.now() / 1000) + 3600);
+      let jobAccount = await assertVaultMatchesLiabilities(tokens);
+      assert.equal(jobAccount.mint?.toBase58(), tokens.mint.toBase58());
+      assert.equal(jobAccount.amount.toNumber(), 1_000_000);
+      assert.equal(await tokenBalance(tokens.clientToken, TOKEN_PROGRAM_ID), 9_000_000);
+
+      await acceptTokenJob(tokens, 100_000);
+      jobAccount = await assertVaultMatchesLiabilities(tokens);
+      assert.equal(jobAccount.providerStake.toNumber(), 100_000);
+
+      await program.methods
+        .submitCompletion(sha256("submission"))
+        .accounts({ provider: providerKp.publicKey, job })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          client: client.publicKey,
+          job,
+          jobVault,
+          treasury,
+          provider: providerKp.publicKey,
+          ...settlementTokenAccounts(tokens),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      const fee = (1_000_000 * 10) / 10_000; // 0.1% protocol fee
+      assert.equal(await tokenBalance(jobTokenVault, TOKEN_PROGRAM_ID), 0);
+      assert.equal(await tokenBalance(tokens.providerToken, TOKEN_PROGRAM_ID), 10_000_000 + 1_000_000 - fee);
+      assert.equal(await tokenBalance(tokens.treasuryToken, TOKEN_PROGRAM_ID), fee);
+      expect((await program.account.jobEscrow.fetch(job)).status).to.deep.equal({ completed: {} });
+      // Volume is kept in lamports, so the token payment counts the job but adds no volume
+      const providerRepAccount = await program.account.agentReputation.fetch(providerRep);
+      assert.equal(providerRepAccount.totalJobsCompleted.toNumber(), 1);
+      assert.equal(providerRepAccount.totalVolume.toNumber(), 0);
+    });
+
+    it("keeps a Token-2022 vault equal to what the escrow owes despite transfer fees", async () => {
+      const tokens = await setupTokenAccounts(TOKEN_2022_PROGRAM_ID);
+      await createTokenJob(tokens, 1_000_000, Math.floor(Date.now() / 1000) + 3600);
+      // The job records what the vault received, not what the client sent
+      let jobAccount = await assertVaultMatchesLiabilities(tokens);
+      assert.equal(jobAccount.amount.toNumber(), 990_000);
+
+      await acceptTokenJob(tokens, 100_000);
+      jobAccount = await assertVaultMatchesLiabilities(tokens);
+      assert.equal(jobAccount.providerStake.toNumber(), 99_000);
+
+      await program.methods
+        .submitCompletion(sha256("submission"))
+        .accounts({ provider: providerKp.publicKey, job })
+        .signers([providerKp])
+        .rpc();
+      const providerBefore = await grossBalance(tokens.providerToken, TOKEN_2022_PROGRAM_ID);
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          client: client.publicKey,
+          job,
+          jobVault,
+          treasury,
+          provider: providerKp.publicKey,
+          ...settlementTokenAccounts(tokens),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      // The vault pays out exactly what it held; transfer fees are withheld from the recipients
+      const fee = Math.floor((990_000 * 10) / 10_000);
+      assert.equal(await tokenBalance(jobTokenVault, TOKEN_2022_PROGRAM_ID), 0);
+      assert.equal(
+        (await grossBalance(tokens.providerToken, TOKEN_2022_PROGRAM_ID)) - providerBefore,
+        990_000 + 99_000 - fee
+      );
+      assert.equal(await grossBalance(tokens.treasuryToken, TOKEN_2022_PROGRAM_ID), fee);
+    });
+
+    it("refunds an expired Token-2022 job in full to the client's token account", async () => {
+      const tokens = await setupTokenAccounts(TOKEN_2022_PROGRAM_ID);
+      await createTokenJob(tokens, 1_000_000, Math.floor(Date.now() / 1000) - 60);
+      await acceptTokenJob(tokens, 100_000);
+      const jobAccount = await assertVaultMatchesLiabilities(tokens);
+      const owed = jobAccount.amount.toNumber() + jobAccount.providerStake.toNumber();
+
+      const clientBefore = await grossBalance(tokens.clientToken, TOKEN_2022_PROGRAM_ID);
+      await program.methods
+        .expireJob()
+        .accounts({
+          job,
+          jobVault,
+          treasury,
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          providerRepVault: findRepVaultPda(providerKp.publicKey)[0],
+          ...settlementTokenAccounts(tokens),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      assert.equal(await tokenBalance(jobTokenVault, TOKEN_2022_PROGRAM_ID), 0);
+      assert.equal((await grossBalance(tokens.clientToken, TOKEN_2022_PROGRAM_ID)) - clientBefore, owed);
+      expect((await program.account.jobEscrow.fetch(job)).status).to.deep.equal({ expired: {} });
+    });
+
+    it("rejects a deposit from a token account of another mint", async () => {
+      const tokens = await setupTokenAccounts(TOKEN_PROGRAM_ID);
+      const other = await setupTokenAccounts(TOKEN_PROGRAM_ID);
+      try {
+        await createTokenJob(tokens, 1_000_000, Math.floor(Date.now() / 1000) + 3600, other.clientToken);
+        assert.fail("Expected mint mismatch error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("MintMismatch");
+      }
+    });
+  });
+
+  describe("ratings", () => {
+    it("lets client and provider rate each other after job completion, but no one else", async () => {
+      const jobId = randomBytes(32);
+      const [job] = findJobPda(jobId);
+      const [jobVault] = findJobVaultPda(job);
+      const [treasury] = findTreasuryPda();
+      const [providerRep] = findReputationPda(providerKp.publicKey);
+
+      // Setup: init reputation
+      const [clientRep] = findReputationPda(client.publicKey);
+      const [clientRepVault] = findRepVaultPda(client.publicKey);
+      const [providerRepVault] = findRepVaultPda(providerKp.publicKey);
+
+      await program.methods
+        .initReputation(new anchor.BN(0.1 * SOL), [])
+        .accounts({
+          agent: client.publicKey,
+          reputation: clientRep,
+          repVault: clientRepVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      await program.methods
+        .initReputation(new anchor.BN(0.1 * SOL), [])
+        .accounts({
+          agent: providerKp.publicKey,
+          reputation: providerRep,
+          repVault: providerRepVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      // Complete a job
+      await program.methods
+        .createJob(
+          jobId,
+          new anchor.BN(0.5 * SOL),
+          new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+          { clientApproval: {} },
+          Array(64).fill(0),
+          sha256("terms")
+        )
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+
+      await program.methods
+        .submitCompletion(sha256("done"))
+        .accounts({ provider: providerKp.publicKey, job })
+        .signers([providerKp])
+        .rpc();
+
+      await program.methods
+        .approveCompletion()
+        .accounts({
+          client: client.publicKey,
+          job,
+          jobVault,
+          treasury,
+          provider: providerKp.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      // Rate
+      const [rating] = PublicKey.findProgramAddressSync(
+        [Buffer.from("rating"), Buffer.from(jobId), client.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .rateJob(jobId, 5, [1, 2], sha256("great work"))
+        .accounts({
+          rater: client.publicKey,
+          job,
+          rateeReputation: providerRep,
+          rating,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      // Check reputation updated
+      const repAccount = await program.account.agentReputation.fetch(providerRep);
+      assert.equal(repAccount.ratingCount.toNumber(), 1);
+      assert.equal(repAccount.avgRating, 500); // 5.00 * 100
+      assert.equal(repAccount.weightedAvgRating, 500);
+      // One rating barely moves the Bayesian average off the 3.00 prior: (10 * 300 + 500) / 11
+      assert.equal(repAccount.bayesianRating, 318);
+
+      // The provider rates the client in the other direction
+      const [providerRating] = PublicKey.findProgramAddressSync(
+        [Buffer.from("rating"), Buffer.from(jobId), providerKp.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .rateJob(jobId, 4, [], sha256("clear brief"))
+        .accounts({
+          rater: providerKp.publicKey,
+          job,
+          rateeReputation: clientRep,
+          rating: providerRating,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+      const clientRepAccount = await program.account.agentReputation.fetch(clientRep);
+      assert.equal(clientRepAccount.avgRating, 400);
+
+      // Outsiders cannot rate either party
+      const outsider = Keypair.generate();
+      await provider.connection.confirmTransaction(await provider.connection.requestAirdrop(outsider.publicKey, SOL));
+      const [outsiderRating] = PublicKey.findProgramAddressSync(
+        [Buffer.from("rating"), Buffer.from(jobId), outsider.publicKey.toBuffer()],
+        program.programId
+      );
+      try {
+        await program.methods
+          .rateJob(jobId, 1, [], sha256("spam"))
+          .accounts({
+            rater: outsider.publicKey,
+            job,
+            rateeReputation: providerRep,
+            rating: outsiderRating,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([outsider])
+          .rpc();
+        assert.fail("Expected authorization error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("Unauthorized");
+      }
+    });
+  });
+
+  describe("arbitration", () => {
+    const findArbiterPda = (authority: PublicKey) =>
+      PublicKey.findProgramAddressSync([Buffer.from("arbiter"), authority.toBuffer()], program.programId);
+    const [registry] = PublicKey.findProgramAddressSync([Buffer.from("arbiter_registry")], program.programId);
+    const findRegistryPagePda = (index: number) => {
+      const indexLe = Buffer.alloc(4);
+      indexLe.writeUInt32LE(index);
+      return PublicKey.findProgramAddressSync([Buffer.from("arbiter_registry_page"), indexLe], program.programId);
+    };
+    const registryPages = async () => {
+      const { pageCount } = await program.account.arbiterRegistry.fetch(registry);
+      return Array.from({ length: pageCount }, (_, index) => findRegistryPagePda(index)[0]);
+    };
+    // The panel is drawn from the hash of a slot fixed when the dispute opened.
+    const waitForSelectionSlot = async (dispute: PublicKey) => {
+      const { selectionSlot } = await program.account.dispute.fetch(dispute);
+      while ((await provider.connection.getSlot()) <= selectionSlot.toNumber()) {
+        await new Promise((resolve) => setTimeout(resolve, 400));
+      }
+    };
+    const addRegistryPage = async () => {
+      const { pageCount } = await program.account.arbiterRegistry.fetch(registry);
+      const [page] = findRegistryPagePda(pageCount);
+      await program.methods
+        .addArbiterRegistryPage()
+        .accounts({ payer: provider.wallet.publicKey, registry, page, systemProgram: anchor.web3.SystemProgram.programId })
+        .rpc();
+      return page;
+    };
+    const registerArbiter = async (kp: Keypair, specializations: number[] = []) => {
+      let registryPage: PublicKey | undefined;
+      for (const page of await registryPages()) {
+        if ((await program.account.arbiterRegistryPage.fetch(page)).entries.length < 16) {
+          registryPage = page;
+          break;
+        }
+      }
+      registryPage = registryPage ?? (await addRegistryPage());
+      await provider.connection.confirmTransaction(await provider.connection.requestAirdrop(kp.publicKey, 2 * SOL));
+      await program.methods
+        .registerArbiter(new anchor.BN(1 * SOL), specializations)
+        .accounts({
+          authority: kp.publicKey,
+          arbiter: findArbiterPda(kp.publicKey)[0],
+          arbiterVault: PublicKey.findProgramAddressSync(
+            [Buffer.from("arbiter_vault"), kp.publicKey.toBuffer()],
+            program.programId
+          )[0],
+          registry,
+          registryPage,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([kp])
+        .rpc();
+      return registryPage;
+    };
+
+    before(async () => {
+      if (!(await provider.connection.getAccountInfo(registry))) {
+        await program.methods
+          .initArbiterRegistry()
+          .accounts({ payer: provider.wallet.publicKey, registry, systemProgram: anchor.web3.SystemProgram.programId })
+          .rpc();
+      }
+    });
+
+    it("draws the dispute panel from the arbiter registry and only lets drawn arbiters vote", async () => {
+      const arbiters = [Keypair.generate(), Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      for (const kp of arbiters) {
+        await registerArbiter(kp);
+      }
+
+      const jobId = randomBytes(32);
+      const [job] = findJobPda(jobId);
+      const [jobVault] = findJobVaultPda(job);
+      const [treasury] = findTreasuryPda();
+      const [dispute] = PublicKey.findProgramAddressSync([Buffer.from("dispute"), job.toBuffer()], program.programId);
+      const [disputeVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dispute_vault"), dispute.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .createJob(
+          jobId,
+          new anchor.BN(0.5 * SOL),
+          new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+          { clientApproval: {} },
+          Array(64).fill(0),
+          sha256("terms")
+        )
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+      await program.methods
+        .acceptJob(new anchor.BN(0.1 * SOL))
+        .accounts({ provider: providerKp.publicKey, job, jobVault, systemProgram: anchor.web3.SystemProgram.programId })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .submitCompletion(sha256("done"))
+        .accounts({ provider: providerKp.publicKey, job })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .raiseDispute(Buffer.from("not what was agreed"), sha256("evidence"))
+        .accounts({
+          raiser: client.publicKey,
+          job,
+          dispute,
+          jobVault,
+          disputeVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+
+      const commit = (kp: Keypair) => {
+        const [arbiter] = findArbiterPda(kp.publicKey);
+        const [voteCommitment] = PublicKey.findProgramAddressSync(
+          [Buffer.from("vote"), dispute.toBuffer(), arbiter.toBuffer()],
+          program.programId
+        );
+        return program.methods
+          .commitVote(randomBytes(32))
+          .accounts({
+            arbiterAuthority: kp.publicKey,
+            arbiter,
+            dispute,
+            voteCommitment,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([kp])
+          .rpc();
+      };
+
+      // No one votes before the panel is drawn
+      try {
+        await commit(arbiters[0]);
+        assert.fail("Expected dispute state error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidDisputeState");
+      }
+
+      // Nor can anyone draw it before the slot that seeds it has passed
+      const select = async () =>
+        program.methods
+          .selectArbiters()
+          .accounts({ dispute, job, registry, slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY })
+          .remainingAccounts((await registryPages()).map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+          .rpc();
+      try {
+        await select();
+        assert.fail("Expected selection slot error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("SelectionSlotPending");
+      }
+
+      await waitForSelectionSlot(dispute);
+      await select();
+
+      const disputeAccount = await program.account.dispute.fetch(dispute);
+      expect(disputeAccount.status).to.deep.equal({ commitPhase: {} });
+      assert.equal(disputeAccount.selectedArbiters.length, 3);
+      assert.equal(new Set(disputeAccount.selectedArbiters.map((a: PublicKey) => a.toBase58())).size, 3);
+      assert.notDeepEqual(disputeAccount.selectionSeed, Array(32).fill(0));
+
+      const isDrawn = (kp: Keypair) =>
+        disputeAccount.selectedArbiters.some((a: PublicKey) => a.equals(findArbiterPda(kp.publicKey)[0]));
+      const drawn = arbiters.filter(isDrawn);
+      const notDrawn = arbiters.filter((kp) => !isDrawn(kp));
+      // Only our four arbiters are registered, so three of them are drawn
+      assert.equal(drawn.length, 3);
+
+      await commit(drawn[0]);
+      try {
+        await commit(notDrawn[0]);
+        assert.fail("Expected undrawn arbiter to be rejected");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ArbiterNotSelected");
+      }
+    });
+
+    it("indexes registered arbiters in registry pages with cumulative stake", async () => {
+      const before = await program.account.arbiterRegistry.fetch(registry);
+      const kp = Keypair.generate();
+      const page = await registerArbiter(kp);
+
+      const after = await program.account.arbiterRegistry.fetch(registry);
+      assert.equal(after.arbiterCount, before.arbiterCount + 1);
+      assert.equal(after.totalStake.sub(before.totalStake).toNumber(), 1 * SOL);
+
+      const [arbiter] = findArbiterPda(kp.publicKey);
+      const pageAccount = await program.account.arbiterRegistryPage.fetch(page);
+      const entry = pageAccount.entries.find((e: any) => e.arbiter.equals(arbiter));
+      assert.ok(entry);
+      assert.equal(entry.stake.toNumber(), 1 * SOL);
+      const pageStake = pageAccount.entries.reduce((sum: number, e: any) => sum + e.stake.toNumber(), 0);
+      assert.equal(pageAccount.totalStake.toNumber(), pageStake);
+      assert.equal((await program.account.arbiter.fetch(arbiter)).registryPage, pageAccount.index);
+
+      // The last page still has room, so no new page can be added yet
+      try {
+        await addRegistryPage();
+        assert.fail("Expected page-not-full error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("ArbiterRegistryPageNotFull");
+      }
+    });
+
+    it("tops up, deactivates and holds arbiter stake through the withdrawal cooldown", async () => {
+      const kp = Keypair.generate();
+      const page = await registerArbiter(kp);
+      const [arbiter] = findArbiterPda(kp.publicKey);
+      const [arbiterVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("arbiter_vault"), kp.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .addArbiterStake(new anchor.BN(0.5 * SOL))
+        .accounts({
+          authority: kp.publicKey,
+          arbiter,
+          arbiterVault,
+          registry,
+          registryPage: page,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([kp])
+        .rpc();
+      assert.equal((await program.account.arbiter.fetch(arbiter)).stake.toNumber(), 1.5 * SOL);
+      const entry = (await program.account.arbiterRegistryPage.fetch(page)).entries.find((e: any) =>
+        e.arbiter.equals(arbiter)
+      );
+      assert.equal(entry.stake.toNumber(), 1.5 * SOL);
+
+      const before = await program.account.arbiterRegistry.fetch(registry);
+      await program.methods
+        .deactivateArbiter()
+        .accounts({ authority: kp.publicKey, arbiter, registry, registryPage: page })
+        .signers([kp])
+        .rpc();
+      const after = await program.account.arbiterRegistry.fetch(registry);
+      assert.equal(after.arbiterCount, before.arbiterCount - 1);
+      assert.equal(before.totalStake.sub(after.totalStake).toNumber(), 1.5 * SOL);
+      const pageAccount = await program.account.arbiterRegistryPage.fetch(page);
+      assert.isUndefined(pageAccount.entries.find((e: any) => e.arbiter.equals(arbiter)));
+      const arbiterAccount = await program.account.arbiter.fetch(arbiter);
+      assert.isFalse(arbiterAccount.active);
+      assert.isAbove(arbiterAccount.withdrawableAt.toNumber(), Math.floor(Date.now() / 1000));
+
+      // Still inside the slashing window
+      try {
+        await program.methods
+          .withdrawArbiterStake()
+          .accounts({ authority: kp.publicKey, arbiter, arbiterVault, systemProgram: anchor.web3.SystemProgram.programId })
+          .signers([kp])
+          .rpc();
+        assert.fail("Expected cooldown error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("StakeUnbonding");
+      }
+    });
+
+    // Opens a dispute on a job in `category`, denominated in `tokens.mint` if given, and draws its panel
+    const openCategorizedDispute = async (category: number, tokens?: Tokens) => {
+      const jobId = randomBytes(32);
+      const [job] = findJobPda(jobId);
+      const [jobVault] = findJobVaultPda(job);
+      const [jobTokenVault] = findJobTokenVaultPda(job);
+      const [treasury] = findTreasuryPda();
+      const [providerRep] = findReputationPda(providerKp.publicKey);
+      const [providerCategoryRep] = findCategoryReputationPda(providerKp.publicKey, category);
+      const [dispute] = PublicKey.findProgramAddressSync([Buffer.from("dispute"), job.toBuffer()], program.programId);
+      const [disputeVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dispute_vault"), dispute.toBuffer()],
+        program.programId
+      );
+      const [disputeTokenVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("dispute_token_vault"), dispute.toBuffer()],
+        program.programId
+      );
+      const mintAccounts = tokens ? { mint: tokens.mint, jobTokenVault, tokenProgram: tokens.tokenProgram } : {};
+      await program.methods
+        .initReputation(new anchor.BN(0.1 * SOL), [])
+        .accounts({
+          agent: providerKp.publicKey,
+          reputation: providerRep,
+          repVault: findRepVaultPda(providerKp.publicKey)[0],
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .initCategoryReputation(category)
+        .accounts({
+          agent: providerKp.publicKey,
+          reputation: providerRep,
+          categoryReputation: providerCategoryRep,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .createJob(
+          jobId,
+          new anchor.BN(tokens ? 1_000_000 : 0.5 * SOL),
+          new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+          { clientApproval: {} },
+          Array(64).fill(0),
+          sha256("terms")
+        )
+        .accounts({
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          job,
+          jobVault,
+          treasury,
+          ...mintAccounts,
+          clientTokenAccount: tokens?.clientToken ?? null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+      await program.methods.setJobCategory(category).accounts({ client: client.publicKey, job }).signers([client]).rpc();
+      await program.methods
+        .acceptJob(new anchor.BN(tokens ? 100_000 : 0.1 * SOL))
+        .accounts({
+          provider: providerKp.publicKey,
+          job,
+          providerCategoryReputation: providerCategoryRep,
+          jobVault,
+          ...mintAccounts,
+          providerTokenAccount: tokens?.providerToken ?? null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .submitCompletion(sha256("done"))
+        .accounts({ provider: providerKp.publicKey, job, submission: findSubmissionPda(job, 0)[0] })
+        .signers([providerKp])
+        .rpc();
+      await program.methods
+        .raiseDispute(Buffer.from("not what was agreed"), sha256("evidence"))
+        .accounts({
+          raiser: client.publicKey,
+          job,
+          dispute,
+          jobVault,
+          disputeVault,
+          ...mintAccounts,
+          disputeTokenVault: tokens ? disputeTokenVault : null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([client])
+        .rpc();
+      await waitForSelectionSlot(dispute);
+      await program.methods
+        .selectArbiters()
+        .accounts({ dispute, job, registry, slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY })
+        .remainingAccounts((await registryPages()).map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+        .rpc();
+      return {
+        job,
+        jobVault,
+        jobTokenVault,
+        treasury,
+        providerRep,
+        providerCategoryRep,
+        dispute,
+        disputeVault,
+        disputeTokenVault,
+      };
+    };
+    const ballot = (kp: Keypair, dispute: PublicKey, vote: boolean) => {
+      const [arbiter] = findArbiterPda(kp.publicKey);
+      const [voteCommitment] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vote"), dispute.toBuffer(), arbiter.toBuffer()],
+        program.programId
+      );
+      const salt = Buffer.from(randomBytes(16));
+      const commitHash = Array.from(
+        crypto
+          .createHash("sha256")
+          .update(Buffer.concat([arbiter.toBuffer(), dispute.toBuffer(), Buffer.from([vote ? 1 : 0]), salt]))
+          .digest()
+      );
+      return { kp, arbiter, voteCommitment, salt, commitHash, vote };
+    };
+    const commitBallot = (dispute: PublicKey, b: ReturnType<typeof ballot>) =>
+      program.methods
+        .commitVote(b.commitHash)
+        .accounts({
+          arbiterAuthority: b.kp.publicKey,
+          arbiter: b.arbiter,
+          dispute,
+          voteCommitment: b.voteCommitment,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([b.kp])
+        .rpc();
+    // resolve_dispute takes [arbiter, arbiter_vault, vote, reward_account, registry_page] per arbiter.
+    // Rewards go to each arbiter's wallet, or to the token account given for it on a token dispute.
+    const arbiterChunks = async (ballots: ReturnType<typeof ballot>[], rewardAccounts?: PublicKey[]) => {
+      const chunks: anchor.web3.AccountMeta[] = [];
+      for (const [i, b] of ballots.entries()) {
+        const { registryPage } = await program.account.arbiter.fetch(b.arbiter);
+        chunks.push(
+          { pubkey: b.arbiter, isSigner: false, isWritable: true },
+          {
+            pubkey: PublicKey.findProgramAddressSync(
+              [Buffer.from("arbiter_vault"), b.kp.publicKey.toBuffer()],
+              program.programId
+            )[0],
+            isSigner: false,
+            isWritable: true,
+          },
+          { pubkey: b.voteCommitment, isSigner: false, isWritable: false },
+          { pubkey: rewardAccounts?.[i] ?? b.kp.publicKey, isSigner: false, isWritable: true },
+          { pubkey: findRegistryPagePda(registryPage)[0], isSigner: false, isWritable: true }
+        );
+      }
+      return chunks;
+    };
+    // Reveals open once the chain clock passes the commit deadline (20 seconds with short-dispute-windows).
+    const waitForCommitDeadline = async (dispute: PublicKey) => {
+      const { commitDeadline } = await program.account.dispute.fetch(dispute);
+      const chainTime = async () => (await provider.connection.getBlockTime(await provider.connection.getSlot())) ?? 0;
+      while ((await chainTime()) <= commitDeadline.toNumber()) {
+        await new Promise((resolve) => setTimeout(resolve, 1000));
+      }
+    };
+    const revealBallot = (dispute: PublicKey, b: ReturnType<typeof ballot>) =>
+      program.methods
+        .revealVote(b.vote, b.salt)
+        .accounts({ arbiterAuthority: b.kp.publicKey, arbiter: b.arbiter, dispute, voteCommitment: b.voteCommitment })
+        .signers([b.kp])
+        .rpc();
+
+    it("scores each arbiter's accuracy when a dispute resolves", async () => {
+      // Only arbiters specialized in the job's category are eligible, so the panel is exactly these three
+      const category = 200;
+      const arbiters = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      for (const kp of arbiters) {
+        await registerArbiter(kp, [category]);
+      }
+
+      const { job, jobVault, treasury, providerRep, providerCategoryRep, dispute, disputeVault } =
+        await openCategorizedDispute(category);
+
+      // Two arbiters side with the client, one with the provider
+      const votes = [false, false, true];
+      const ballots = arbiters.map((kp, i) => ballot(kp, dispute, votes[i]));
+      for (const b of ballots) {
+        await commitBallot(dispute, b);
+      }
+      assert.equal((await program.account.arbiter.fetch(ballots[0].arbiter)).openCases, 1);
+      await waitForCommitDeadline(dispute);
+      for (const b of ballots) {
+        await revealBallot(dispute, b);
+      }
+
+      const resolve = async (included: ReturnType<typeof ballot>[]) =>
+        program.methods
+          .resolveDispute()
+          .accounts({
+            dispute,
+            job,
+            jobVault,
+            disputeVault,
+            treasury,
+            client: client.publicKey,
+            provider: providerKp.publicKey,
+            providerReputation: providerRep,
+            providerRepVault: findRepVaultPda(providerKp.publicKey)[0],
+            providerCategoryReputation: providerCategoryRep,
+            registry,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .remainingAccounts(await arbiterChunks(included))
+          .rpc();
+
+      // Leaving out an arbiter that committed would strand its open case
+      try {
+        await resolve(ballots.slice(0, 2));
+        assert.fail("Expected invalid arbiter set error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidArbiterSet");
+      }
+      // Passing an arbiter twice would pay or slash it twice
+      try {
+        await resolve([...ballots, ballots[0]]);
+        assert.fail("Expected invalid arbiter set error");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InvalidArbiterSet");
+      }
+      // The whole panel has revealed, so resolution does not wait for the reveal deadline
+      await resolve(ballots);
+
+      const [majority, , minority] = await Promise.all(ballots.map((b) => program.account.arbiter.fetch(b.arbiter)));
+      assert.equal(majority.casesJudged.toNumber(), 1);
+      assert.equal(majority.accuracyScore, 600); // 500 moved 20% toward 1000
+      assert.equal(majority.openCases, 0);
+      assert.equal(minority.casesJudged.toNumber(), 1);
+      assert.equal(minority.accuracyScore, 400); // 500 moved 20% toward 0
+      assert.equal(minority.stake.toNumber(), 0.99 * SOL); // slashed 1% for voting against the majority
+      // Below the minimum stake, the slashed arbiter leaves the registry and can no longer be drawn
+      assert.isFalse(minority.active);
+      const page = await program.account.arbiterRegistryPage.fetch(findRegistryPagePda(minority.registryPage)[0]);
+      assert.isFalse(page.entries.some((e: any) => e.arbiter.equals(ballots[2].arbiter)));
+      assert.isTrue(page.entries.some((e: any) => e.arbiter.equals(ballots[0].arbiter)));
+    });
+
+    it("moves a Token-2022 dispute fee and payout through the token vaults", async () => {
+      const category = 203;
+      const arbiters = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      const pages: PublicKey[] = [];
+      for (const kp of arbiters) {
+        pages.push(await registerArbiter(kp, [category]));
+      }
+      const tokens = await setupTokenAccounts(TOKEN_2022_PROGRAM_ID);
+      const {
+        job,
+        jobVault,
+        jobTokenVault,
+        treasury,
+        providerRep,
+        providerCategoryRep,
+        dispute,
+        disputeVault,
+        disputeTokenVault,
+      } = await openCategorizedDispute(category, tokens);
+
+      // The 1% dispute fee of the 990_000 the vault received left it, and the job owes that much less
+      const jobAccount = await program.account.jobEscrow.fetch(job);
+      const owed = jobAccount.amount.toNumber() + jobAccount.providerStake.toNumber();
+      assert.equal(jobAccount.amount.toNumber(), 990_000 - 9_900);
+      assert.equal(await tokenBalance(jobTokenVault, TOKEN_2022_PROGRAM_ID), owed);
+      assert.equal(await grossBalance(disputeTokenVault, TOKEN_2022_PROGRAM_ID), 9_900);
+
+      const ballots = arbiters.map((kp) => ballot(kp, dispute, false));
+      for (const b of ballots) {
+        await commitBallot(dispute, b);
+      }
+      await waitForCommitDeadline(dispute);
+      for (const b of ballots) {
+        await revealBallot(dispute, b);
+      }
+      // Stake added after revealing does not buy a bigger share of the fee
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(arbiters[0].publicKey, 4 * SOL)
+      );
+      await program.methods
+        .addArbiterStake(new anchor.BN(3 * SOL))
+        .accounts({
+          authority: arbiters[0].publicKey,
+          arbiter: findArbiterPda(arbiters[0].publicKey)[0],
+          arbiterVault: PublicKey.findProgramAddressSync(
+            [Buffer.from("arbiter_vault"), arbiters[0].publicKey.toBuffer()],
+            program.programId
+          )[0],
+          registry,
+          registryPage: pages[0],
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([arbiters[0]])
+        .rpc();
+
+      const rewardAccounts: PublicKey[] = [];
+      for (const kp of arbiters) {
+        rewardAccounts.push(await tokens.tokenAccount(kp.publicKey));
+      }
+      const pool = await tokenBalance(disputeTokenVault, TOKEN_2022_PROGRAM_ID);
+      const clientBefore = await grossBalance(tokens.clientToken, TOKEN_2022_PROGRAM_ID);
+      await program.methods
+        .resolveDispute()
+        .accounts({
+          dispute,
+          job,
+          jobVault,
+          disputeVault,
+          treasury,
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          providerReputation: providerRep,
+          providerRepVault: findRepVaultPda(providerKp.publicKey)[0],
+          providerCategoryReputation: providerCategoryRep,
+          mint: tokens.mint,
+          jobTokenVault,
+          disputeTokenVault,
+          clientTokenAccount: tokens.clientToken,
+          providerTokenAccount: tokens.providerToken,
+          treasuryTokenAccount: tokens.treasuryToken,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          registry,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(await arbiterChunks(ballots, rewardAccounts))
+        .rpc();
+
+      // The client won: the job vault refunds everything it owed, and the panel splits the fee evenly
+      assert.equal(await tokenBalance(jobTokenVault, TOKEN_2022_PROGRAM_ID), 0);
+      assert.equal((await grossBalance(tokens.clientToken, TOKEN_2022_PROGRAM_ID)) - clientBefore, owed);
+      const share = Math.floor(pool / 3);
+      for (const account of rewardAccounts) {
+        assert.equal(await grossBalance(account, TOKEN_2022_PROGRAM_ID), share);
+      }
+      assert.equal(await tokenBalance(disputeTokenVault, TOKEN_2022_PROGRAM_ID), pool - 3 * share);
+    });
+
+    it("pays the provider on a vote without a supermajority, even when resolved before the reveal deadline", async () => {
+      const category = 202;
+      const arbiters = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      const pages: PublicKey[] = [];
+      for (const kp of arbiters) {
+        pages.push(await registerArbiter(kp, [category]));
+      }
+      // Four times the stake is twice the vote weight, so one provider vote splits two client votes 50/50
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(arbiters[2].publicKey, 4 * SOL)
+      );
+      await program.methods
+        .addArbiterStake(new anchor.BN(3 * SOL))
+        .accounts({
+          authority: arbiters[2].publicKey,
+          arbiter: findArbiterPda(arbiters[2].publicKey)[0],
+          arbiterVault: PublicKey.findProgramAddressSync(
+            [Buffer.from("arbiter_vault"), arbiters[2].publicKey.toBuffer()],
+            program.programId
+          )[0],
+          registry,
+          registryPage: pages[2],
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([arbiters[2]])
+        .rpc();
+
+      const { job, jobVault, treasury, providerRep, providerCategoryRep, dispute, disputeVault } =
+        await openCategorizedDispute(category);
+      const votes = [false, false, true];
+      const ballots = arbiters.map((kp, i) => ballot(kp, dispute, votes[i]));
+      for (const b of ballots) {
+        await commitBallot(dispute, b);
+      }
+      await waitForCommitDeadline(dispute);
+      for (const b of ballots) {
+        await revealBallot(dispute, b);
+      }
+
+      await program.methods
+        .resolveDispute()
+        .accounts({
+          dispute,
+          job,
+          jobVault,
+          disputeVault,
+          treasury,
+          client: client.publicKey,
+          provider: providerKp.publicKey,
+          providerReputation: providerRep,
+          providerRepVault: findRepVaultPda(providerKp.publicKey)[0],
+          providerCategoryReputation: providerCategoryRep,
+          registry,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(await arbiterChunks(ballots))
+        .rpc();
+
+      // Resolved as soon as the whole panel revealed, with neither side at 60% of the weight
+      const disputeAccount = await program.account.dispute.fetch(dispute);
+      const clientWeight = disputeAccount.clientWeight.toNumber();
+      const providerWeight = disputeAccount.providerWeight.toNumber();
+      assert.equal(disputeAccount.revealCount, 3);
+      assert.isBelow((clientWeight * 100) / (clientWeight + providerWeight), 60);
+      assert.isBelow((providerWeight * 100) / (clientWeight + providerWeight), 60);
+      assert.isFalse(disputeAccount.resolvedInFavorOfClient);
+      expect((await program.account.jobEscrow.fetch(job)).status).to.deep.equal({ resolved: {} });
+    });
+
+    it("keeps reveals closed until the commit deadline and resolution closed until voting ends", async () => {
+      const category = 201;
+      const arbiters = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      for (const kp of arbiters) {
+        await registerArbiter(kp, [category]);
+      }
+      const { job, jobVault, treasury, providerRep, providerCategoryRep, dispute, disputeVault } =
+        await openCategorizedDispute(category);
+      const ballots = arbiters.map((kp) => ballot(kp, dispute, true));
+
+      for (const b of ballots) {
+        await commitBallot(dispute, b);
+      }
+      const disputeAccount = await program.account.dispute.fetch(dispute);
+      expect(disputeAccount.status).to.deep.equal({ commitPhase: {} });
+      assert.equal(disputeAccount.commitCount, 3);
+      assert.equal((await program.account.arbiter.fetch(ballots[0].arbiter)).openCases, 1);
+
+      // The whole panel has committed, but the commit deadline has not passed
+      try {
+        await revealBallot(dispute, ballots[0]);
+        assert.fail("Expected reveal to wait for the commit deadline");
+      } catch (err: any) {
+        expect(err.toString()).to.include("DeadlineNotReached");
+      }
+
+      // No arbiter has revealed and the reveal deadline has not passed
+      try {
+        await program.methods
+          .resolveDispute()
+          .accounts({
+            dispute,
+            job,
+            jobVault,
+            disputeVault,
+            treasury,
+            client: client.publicKey,
+            provider: providerKp.publicKey,
+            providerReputation: providerRep,
+            providerRepVault: findRepVaultPda(providerKp.publicKey)[0],
+            providerCategoryReputation: providerCategoryRep,
+            registry,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Expected resolution to wait for the reveal phase");
+      } catch (err: any) {
+        expect(err.toString()).to.include("DeadlineNotReached");
+      }
     });
   });
 });